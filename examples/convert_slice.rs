@@ -0,0 +1,38 @@
+//! Compares converting a slice in place with `convert_slice_be` against a plain `u32::from_be` loop
+//!
+//! Run with `cargo run --release --example convert_slice`, both should take about the same time as
+//! the compiler vectorizes the byte swaps in either case
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const LEN: usize = 1_000_000;
+const ROUNDS: u32 = 200;
+
+/// Runs `f` over `items` for every round, returning the total time taken
+fn time(items: &mut [u32], mut f: impl FnMut(&mut [u32])) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f(black_box(&mut *items));
+    }
+
+    start.elapsed()
+}
+
+fn main() {
+    let mut items: Vec<u32> = (0..LEN as u32).collect();
+
+    let convert = time(&mut items, tinybit::convert_slice_be);
+    let from_be = time(&mut items, |items| {
+        for item in items.iter_mut() {
+            *item = u32::from_be(*item);
+        }
+    });
+
+    // An even number of rounds with each method leaves the values untouched
+    assert!(items.iter().enumerate().all(|(i, &item)| item == i as u32));
+
+    println!("{} rounds over {} u32", ROUNDS, LEN);
+    println!("convert_slice_be: {:?}", convert);
+    println!("u32::from_be:     {:?}", from_be);
+}
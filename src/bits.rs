@@ -0,0 +1,456 @@
+//! Bit granular readers and writers
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+
+#[cfg(feature = "std")]
+use crate::write_full;
+use crate::EndianError;
+
+/// The order bits are packed into each byte
+///
+/// # Remarks
+/// Implemented by the zero sized [`MsbFirst`] and [`LsbFirst`] markers
+///
+/// This trait is sealed and can not be implemented outside of this crate
+pub trait BitOrder: Copy + Default + private::Sealed {
+    /// True when the most significant bit of each byte is read/written first
+    const MSB_FIRST: bool;
+}
+
+/// Bits are packed starting at the most significant bit of each byte, as used by network protocols
+/// and JPEG
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MsbFirst;
+
+/// Bits are packed starting at the least significant bit of each byte, as used by DEFLATE
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LsbFirst;
+
+impl BitOrder for MsbFirst {
+    const MSB_FIRST: bool = true;
+}
+
+impl BitOrder for LsbFirst {
+    const MSB_FIRST: bool = false;
+}
+
+/// A source of bytes for a [`BitReader`]
+///
+/// # Remarks
+/// Implemented for every `io::Read` with the `std` feature and for byte slices without it
+pub trait BitSource {
+    /// Reads at most `buf.len()` bytes into `buf`
+    ///
+    /// # Returns
+    /// Ok - The number of bytes read, 0 once the source is exhausted
+    /// Err - When the source fails
+    fn pull(&mut self, buf: &mut [u8]) -> Result<usize, EndianError>;
+}
+
+/// A destination for the bytes of a [`BitWriter`]
+///
+/// # Remarks
+/// Implemented for every `io::Write` with the `std` feature and for byte slices and `Vec<u8>` without it
+pub trait BitSink {
+    /// Writes all of `bytes`
+    ///
+    /// # Returns
+    /// Ok when every byte was written
+    /// Err when the destination fails or runs out of space
+    fn push(&mut self, bytes: &[u8]) -> Result<(), EndianError>;
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> BitSource for R {
+    fn pull(&mut self, buf: &mut [u8]) -> Result<usize, EndianError> {
+        loop {
+            match self.read(buf) {
+                Ok(count) => return Ok(count),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(EndianError::io::<[u8]>(buf.len(), 0, e))
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl BitSource for &[u8] {
+    fn pull(&mut self, buf: &mut [u8]) -> Result<usize, EndianError> {
+        let count = buf.len().min(self.len());
+        let (head, tail) = self.split_at(count);
+        buf[..count].copy_from_slice(head);
+        *self = tail;
+        Ok(count)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> BitSink for W {
+    fn push(&mut self, bytes: &[u8]) -> Result<(), EndianError> {
+        write_full::<[u8], _>(self, bytes)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl BitSink for &mut [u8] {
+    fn push(&mut self, bytes: &[u8]) -> Result<(), EndianError> {
+        if self.len() < bytes.len() {
+            return Err(EndianError::EndOfStream(0));
+        }
+
+        let (head, tail) = core::mem::take(self).split_at_mut(bytes.len());
+        head.copy_from_slice(bytes);
+        *self = tail;
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+impl BitSink for Vec<u8> {
+    fn push(&mut self, bytes: &[u8]) -> Result<(), EndianError> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// Reads fields of any number of bits from a byte source
+///
+/// # Remarks
+/// Bytes are pulled from the source 64 bits at a time into an internal buffer, so the source may be
+/// read ahead of the last bit returned. A read which can not be satisfied fails with
+/// [`EndianError::EndOfStream`] containing the number of bytes pulled from the source and consumes no
+/// bits
+///
+/// The bit order defaults to [`MsbFirst`]
+#[derive(Clone, Debug)]
+pub struct BitReader<R, O = MsbFirst> {
+    inner: R,
+    buf: u128,
+    len: u32,
+    pulled: usize,
+    order: O
+}
+
+impl<R: BitSource> BitReader<R> {
+    /// Creates a reader taking bits from the most significant end of each byte
+    pub fn new(inner: R) -> Self {
+        Self::with_order(inner, MsbFirst)
+    }
+}
+
+impl<R: BitSource, O: BitOrder> BitReader<R, O> {
+    /// Creates a reader taking bits in `order`
+    pub fn with_order(inner: R, order: O) -> Self {
+        BitReader {
+            inner,
+            buf: 0,
+            len: 0,
+            pulled: 0,
+            order
+        }
+    }
+
+    /// Reads a single bit
+    pub fn read_bit(&mut self) -> Result<bool, EndianError> {
+        self.read_bits(1).map(|bit| bit != 0)
+    }
+
+    /// Reads an unsigned field of `count` bits
+    ///
+    /// # Remarks
+    /// Panics when `count` is greater than 64
+    pub fn read_bits(&mut self, count: u32) -> Result<u64, EndianError> {
+        assert!(count <= 64, "can not read more than 64 bits at once");
+
+        while self.len < count {
+            if !self.refill()? {
+                return Err(EndianError::EndOfStream(self.pulled));
+            }
+        }
+
+        Ok(self.take(count))
+    }
+
+    /// Reads a two's complement field of `count` bits, sign extending it
+    ///
+    /// # Remarks
+    /// Panics when `count` is greater than 64
+    pub fn read_signed_bits(&mut self, count: u32) -> Result<i64, EndianError> {
+        let value = self.read_bits(count)?;
+        if count == 0 {
+            return Ok(0);
+        }
+
+        let shift = 64 - count;
+        Ok(((value << shift) as i64) >> shift)
+    }
+
+    /// Discards the bits left in the current byte
+    pub fn align_to_byte(&mut self) {
+        let partial = self.len % 8;
+        self.take(partial);
+    }
+
+    /// Returns true when the next bit read starts a new byte
+    pub fn is_aligned(&self) -> bool {
+        self.len.is_multiple_of(8)
+    }
+
+    /// The number of bits read so far
+    pub fn bit_position(&self) -> u64 {
+        self.pulled as u64 * 8 - u64::from(self.len)
+    }
+
+    /// The bit order of the reader
+    pub fn order(&self) -> O {
+        self.order
+    }
+
+    /// Gets a reference to the inner source
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Consumes the reader, returning the inner source
+    ///
+    /// # Remarks
+    /// Bytes already pulled into the internal buffer are lost
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Pulls up to 64 bits from the source into the buffer
+    ///
+    /// # Returns
+    /// Ok with false once the source is exhausted
+    fn refill(&mut self) -> Result<bool, EndianError> {
+        let mut bytes = [0; 8];
+        let room = ((128 - self.len) / 8).min(8) as usize;
+        let count = self.inner.pull(&mut bytes[..room]).map_err(|e| e.offset_by(self.pulled))?;
+
+        for &byte in &bytes[..count] {
+            if O::MSB_FIRST {
+                self.buf |= u128::from(byte) << (120 - self.len);
+            } else {
+                self.buf |= u128::from(byte) << self.len;
+            }
+
+            self.len += 8;
+        }
+
+        self.pulled += count;
+        Ok(count != 0)
+    }
+
+    /// Removes the next `count` buffered bits, `count` must not exceed the number of buffered bits
+    fn take(&mut self, count: u32) -> u64 {
+        if count == 0 {
+            return 0;
+        }
+
+        let value = if O::MSB_FIRST {
+            let value = (self.buf >> (128 - count)) as u64;
+            self.buf <<= count;
+            value
+        } else {
+            let value = (self.buf & ((1 << count) - 1)) as u64;
+            self.buf >>= count;
+            value
+        };
+
+        self.len -= count;
+        value
+    }
+}
+
+/// Writes fields of any number of bits to a byte destination
+///
+/// # Remarks
+/// Bits are collected in an internal buffer and written 64 bits at a time, call
+/// [`flush`](BitWriter::flush) or [`finish`](BitWriter::finish) once done so the trailing bits are
+/// written, padded with zeros to a whole byte. Dropping the writer discards any buffered bits
+///
+/// The bit order defaults to [`MsbFirst`]
+#[derive(Clone, Debug)]
+pub struct BitWriter<W, O = MsbFirst> {
+    inner: W,
+    buf: u128,
+    len: u32,
+    pushed: usize,
+    order: O
+}
+
+impl<W: BitSink> BitWriter<W> {
+    /// Creates a writer filling each byte from its most significant bit
+    pub fn new(inner: W) -> Self {
+        Self::with_order(inner, MsbFirst)
+    }
+}
+
+impl<W: BitSink, O: BitOrder> BitWriter<W, O> {
+    /// Creates a writer filling each byte in `order`
+    pub fn with_order(inner: W, order: O) -> Self {
+        BitWriter {
+            inner,
+            buf: 0,
+            len: 0,
+            pushed: 0,
+            order
+        }
+    }
+
+    /// Writes a single bit
+    pub fn write_bit(&mut self, bit: bool) -> Result<(), EndianError> {
+        self.write_bits(u64::from(bit), 1)
+    }
+
+    /// Writes `value` as an unsigned field of `count` bits
+    ///
+    /// # Remarks
+    /// Panics when `count` is greater than 64
+    ///
+    /// # Returns
+    /// Ok when the field was buffered
+    /// Err - [`EndianError::Overflow`] when `value` does not fit in `count` bits, or the error of the
+    /// destination when buffered bytes could not be written
+    pub fn write_bits(&mut self, value: u64, count: u32) -> Result<(), EndianError> {
+        assert!(count <= 64, "can not write more than 64 bits at once");
+
+        if count < 64 && value >> count != 0 {
+            return Err(self.overflow());
+        }
+
+        self.put(value, count)
+    }
+
+    /// Writes `value` as a two's complement field of `count` bits
+    ///
+    /// # Remarks
+    /// Panics when `count` is greater than 64
+    ///
+    /// # Returns
+    /// Ok when the field was buffered
+    /// Err - [`EndianError::Overflow`] when `value` does not fit in `count` bits, or the error of the
+    /// destination when buffered bytes could not be written
+    pub fn write_signed_bits(&mut self, value: i64, count: u32) -> Result<(), EndianError> {
+        assert!(count <= 64, "can not write more than 64 bits at once");
+
+        let fits = match count {
+            0 => value == 0,
+            64 => true,
+            _ => value >> (count - 1) == 0 || value >> (count - 1) == -1
+        };
+
+        if !fits {
+            return Err(self.overflow());
+        }
+
+        let mask = if count == 64 { u64::MAX } else { (1 << count) - 1 };
+        self.put(value as u64 & mask, count)
+    }
+
+    /// Pads the current byte with zero bits
+    pub fn align_to_byte(&mut self) {
+        self.len += (8 - self.len % 8) % 8;
+    }
+
+    /// Returns true when the next bit written starts a new byte
+    pub fn is_aligned(&self) -> bool {
+        self.len.is_multiple_of(8)
+    }
+
+    /// The number of bits written so far, including padding
+    pub fn bit_position(&self) -> u64 {
+        self.pushed as u64 * 8 + u64::from(self.len)
+    }
+
+    /// Pads the current byte with zero bits and writes every buffered byte to the destination
+    pub fn flush(&mut self) -> Result<(), EndianError> {
+        self.align_to_byte();
+        while self.len >= 64 {
+            self.emit()?;
+        }
+
+        let count = (self.len / 8) as usize;
+        self.inner.push(&self.head()[..count]).map_err(|e| e.offset_by(self.pushed))?;
+        self.buf = 0;
+        self.len = 0;
+        self.pushed += count;
+        Ok(())
+    }
+
+    /// Flushes the writer and returns the destination
+    pub fn finish(mut self) -> Result<W, EndianError> {
+        self.flush()?;
+        Ok(self.inner)
+    }
+
+    /// The bit order of the writer
+    pub fn order(&self) -> O {
+        self.order
+    }
+
+    /// Gets a reference to the destination
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Appends the low `count` bits of `value`, which must not have any higher bits set
+    fn put(&mut self, value: u64, count: u32) -> Result<(), EndianError> {
+        // Keeps room for a full 64 bit field, bits stay buffered if the destination fails
+        if self.len >= 64 {
+            self.emit()?;
+        }
+
+        if count == 0 {
+            return Ok(());
+        }
+
+        if O::MSB_FIRST {
+            self.buf |= u128::from(value) << (128 - self.len - count);
+        } else {
+            self.buf |= u128::from(value) << self.len;
+        }
+
+        self.len += count;
+        Ok(())
+    }
+
+    /// Writes the first 64 buffered bits to the destination
+    fn emit(&mut self) -> Result<(), EndianError> {
+        self.inner.push(&self.head()).map_err(|e| e.offset_by(self.pushed))?;
+        if O::MSB_FIRST {
+            self.buf <<= 64;
+        } else {
+            self.buf >>= 64;
+        }
+
+        self.len -= 64;
+        self.pushed += 8;
+        Ok(())
+    }
+
+    /// The first 64 buffered bits in the order they are written
+    fn head(&self) -> [u8; 8] {
+        if O::MSB_FIRST {
+            ((self.buf >> 64) as u64).to_be_bytes()
+        } else {
+            (self.buf as u64).to_le_bytes()
+        }
+    }
+
+    /// Creates an overflow error for a field starting at the current byte
+    fn overflow(&self) -> EndianError {
+        EndianError::overflow("bit field").offset_by(self.pushed + (self.len / 8) as usize)
+    }
+}
+
+mod private {
+    pub trait Sealed { }
+
+    impl Sealed for super::MsbFirst { }
+    impl Sealed for super::LsbFirst { }
+}
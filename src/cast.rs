@@ -0,0 +1,100 @@
+//! Zero-copy views of byte slices as typed values
+
+use core::mem;
+use core::num::Wrapping;
+use core::slice;
+
+use crate::storage::*;
+
+/// Marker for types which can be viewed directly from bytes without copying
+///
+/// # Remarks
+/// Implemented for the primitive integer and floating point types, the fixed byte order storage
+/// types (e.g. [`U32Be`]) and arrays of them. Structs made of such fields can derive it with the
+/// `derive` feature, which verifies every requirement below at compile time
+///
+/// # Safety
+/// Implementors must guarantee the following
+/// - Every bit pattern of `mem::size_of::<Self>()` bytes is a valid value of the type
+/// - The type contains no padding bytes
+/// - The type contains no interior mutability (e.g. `Cell`)
+/// - The type has a defined layout (e.g. `#[repr(C)]` or `#[repr(transparent)]`)
+pub unsafe trait FromBytes: Sized { }
+
+macro_rules! impl_from_bytes {
+    ($($ty:ty),*) => {
+        $(unsafe impl FromBytes for $ty { })*
+    };
+}
+
+impl_from_bytes!(u8, u16, u32, u64, u128);
+impl_from_bytes!(i8, i16, i32, i64, i128);
+impl_from_bytes!(f32, f64);
+impl_from_bytes!(U16Le, U32Le, U64Le, U128Le, I16Le, I32Le, I64Le, I128Le, F32Le, F64Le);
+impl_from_bytes!(U16Be, U32Be, U64Be, U128Be, I16Be, I32Be, I64Be, I128Be, F32Be, F64Be);
+
+unsafe impl<T: FromBytes> FromBytes for Wrapping<T> { }
+unsafe impl<T: FromBytes, const N: usize> FromBytes for [T; N] { }
+
+/// Views `bytes` as a `T` without copying
+///
+/// # Returns
+/// Some when `bytes` is exactly `mem::size_of::<T>()` bytes long and suitably aligned for `T`
+/// None otherwise
+pub fn ref_from_bytes<T: FromBytes>(bytes: &[u8]) -> Option<&T> {
+    if bytes.len() != mem::size_of::<T>() || !is_aligned::<T>(bytes.as_ptr()) {
+        return None;
+    }
+
+    // Size and alignment were checked above, FromBytes guarantees any contents are valid
+    Some(unsafe { &*(bytes.as_ptr() as *const T) })
+}
+
+/// Views `bytes` as a mutable `T` without copying
+///
+/// # Returns
+/// Some when `bytes` is exactly `mem::size_of::<T>()` bytes long and suitably aligned for `T`
+/// None otherwise
+pub fn mut_from_bytes<T: FromBytes>(bytes: &mut [u8]) -> Option<&mut T> {
+    if bytes.len() != mem::size_of::<T>() || !is_aligned::<T>(bytes.as_ptr()) {
+        return None;
+    }
+
+    // As above, and since T has no padding any value written through it leaves `bytes` initialized
+    Some(unsafe { &mut *(bytes.as_mut_ptr() as *mut T) })
+}
+
+/// Views `bytes` as a slice of `T` without copying
+///
+/// # Returns
+/// Some when the length of `bytes` is a multiple of `mem::size_of::<T>()` and `bytes` is suitably
+/// aligned for `T`
+/// None otherwise, including when `T` is zero sized
+pub fn slice_from_bytes<T: FromBytes>(bytes: &[u8]) -> Option<&[T]> {
+    let size = mem::size_of::<T>();
+    if size == 0 || !bytes.len().is_multiple_of(size) || !is_aligned::<T>(bytes.as_ptr()) {
+        return None;
+    }
+
+    Some(unsafe { slice::from_raw_parts(bytes.as_ptr() as *const T, bytes.len() / size) })
+}
+
+/// Views `bytes` as a mutable slice of `T` without copying
+///
+/// # Returns
+/// Some when the length of `bytes` is a multiple of `mem::size_of::<T>()` and `bytes` is suitably
+/// aligned for `T`
+/// None otherwise, including when `T` is zero sized
+pub fn slice_from_bytes_mut<T: FromBytes>(bytes: &mut [u8]) -> Option<&mut [T]> {
+    let size = mem::size_of::<T>();
+    if size == 0 || !bytes.len().is_multiple_of(size) || !is_aligned::<T>(bytes.as_ptr()) {
+        return None;
+    }
+
+    Some(unsafe { slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut T, bytes.len() / size) })
+}
+
+/// Returns true when `ptr` is suitably aligned for a `T`
+fn is_aligned<T>(ptr: *const u8) -> bool {
+    ptr.cast::<T>().is_aligned()
+}
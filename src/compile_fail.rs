@@ -0,0 +1,78 @@
+//! Inputs which must be rejected at compile time
+//!
+//! Types with invalid bit patterns only implement `TryEndian`, so they can not be read through `Endian`
+//!
+//! ```compile_fail
+//! let _ = <bool as tinybit::Endian>::read_le_from(&[1]);
+//! ```
+//!
+//! ```compile_fail
+//! let _ = <char as tinybit::Endian>::read_le_from(&[0x41, 0, 0, 0]);
+//! ```
+//!
+//! Only `Option<NonZero*>` has a value to decode zero into
+//!
+//! ```compile_fail
+//! let _ = <Option<u8> as tinybit::Endian>::read_le_from(&[0]);
+//! ```
+//!
+//! `usize` and `isize` have no portable width and are written through `write_usize_as` instead
+//!
+//! ```compile_fail
+//! let _ = tinybit::Endian::write_le_into(&1usize, &mut [0; 8]);
+//! ```
+//!
+//! ```compile_fail
+//! let _ = <isize as tinybit::Endian>::read_le_from(&[0; 8]);
+//! ```
+
+// Derive inputs which must be rejected
+#[cfg(feature = "derive")]
+mod derive {
+    //! A missing `#[repr]` on an enum
+    //!
+    //! ```compile_fail
+    //! #[derive(tinybit::TryEndian)]
+    //! enum Message {
+    //!     Ping,
+    //!     Data(u32)
+    //! }
+    //! ```
+    //!
+    //! More than one `#[endian(unknown)]` variant
+    //!
+    //! ```compile_fail
+    //! #[derive(tinybit::Endian)]
+    //! #[repr(u8)]
+    //! enum Command {
+    //!     Start,
+    //!     #[endian(unknown)]
+    //!     Unknown(u8),
+    //!     #[endian(unknown)]
+    //!     Other(u8)
+    //! }
+    //! ```
+    //!
+    //! An `#[endian(unknown)]` variant holding a tag wider than the `#[repr]`
+    //!
+    //! ```compile_fail
+    //! #[derive(tinybit::Endian)]
+    //! #[repr(u8)]
+    //! enum Command {
+    //!     Start,
+    //!     #[endian(unknown)]
+    //!     Unknown(u16)
+    //! }
+    //! ```
+    //!
+    //! A `FromBytes` struct with padding between its fields
+    //!
+    //! ```compile_fail
+    //! #[derive(tinybit::FromBytes)]
+    //! #[repr(C)]
+    //! struct Padded {
+    //!     tag: u8,
+    //!     value: u32
+    //! }
+    //! ```
+}
@@ -0,0 +1,1304 @@
+//! Cursors for reading and writing [`Endian`] values directly from byte slices
+
+use core::convert::{TryFrom, TryInto};
+use core::ffi::CStr;
+use core::mem;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::string;
+use crate::text::{self, TextEncoding};
+use crate::uint;
+use crate::varint::{self, Varint};
+use crate::{BigEndian, ByteOrder, Endian, EndianError, Endianness, LittleEndian, Tagged, TryEndian};
+
+/// A zero-copy cursor reading [`Endian`] values out of a byte slice
+///
+/// # Remarks
+/// Every read is bounds checked before the bytes are loaded, a read running past the end of the slice
+/// fails with [`EndianError::EndOfStream`] containing the number of bytes read so far and leaves the
+/// position untouched
+/// 
+/// Besides the explicit little and big endian methods the reader stores a byte order used by
+/// [`read`](SliceReader::read), defaulting to
+/// [`Endianness::Native`]. Using one of the [`ByteOrder`] markers instead fixes the byte order at
+/// compile time
+#[derive(Clone, Debug)]
+pub struct SliceReader<'a, B = Endianness> {
+    buf: &'a [u8],
+    pos: usize,
+    order: B
+}
+
+impl<'a> SliceReader<'a> {
+    /// Creates a reader starting at the beginning of `buf`
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self::with_order(buf, Endianness::Native)
+    }
+}
+
+impl<'a, B: ByteOrder> SliceReader<'a, B> {
+    /// Creates a reader starting at the beginning of `buf` which reads values in `order`
+    pub fn with_order(buf: &'a [u8], order: B) -> Self {
+        SliceReader {
+            buf,
+            pos: 0,
+            order
+        }
+    }
+
+    /// Reads a value in the configured byte order
+    pub fn read<T: Endian>(&mut self) -> Result<T, EndianError> {
+        if self.order.is_big() {
+            self.read_be()
+        } else {
+            self.read_le()
+        }
+    }
+
+    /// Reads a value from its little endian bytes
+    pub fn read_le<T: Endian>(&mut self) -> Result<T, EndianError> {
+        let bytes = self.read_bytes(T::SIZE)?;
+
+        // The slice returned by read_bytes is exactly T::SIZE bytes long
+        Ok(unsafe { T::read_le_unchecked(bytes.as_ptr()) })
+    }
+
+    /// Reads a value from its big endian bytes
+    pub fn read_be<T: Endian>(&mut self) -> Result<T, EndianError> {
+        let bytes = self.read_bytes(T::SIZE)?;
+
+        // The slice returned by read_bytes is exactly T::SIZE bytes long
+        Ok(unsafe { T::read_be_unchecked(bytes.as_ptr()) })
+    }
+
+    /// Reads a validated value such as a `bool` or `char` in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The value
+    /// Err - [`EndianError::InvalidValue`] when the bytes are not a valid value, or when the slice ends
+    /// early. The position is left unchanged on failure
+    pub fn try_read<T: TryEndian>(&mut self) -> Result<T, EndianError> {
+        if self.order.is_big() {
+            self.try_read_be()
+        } else {
+            self.try_read_le()
+        }
+    }
+
+    /// Reads a validated value from its little endian bytes
+    /// 
+    /// # Returns
+    /// See [`try_read`](SliceReader::try_read)
+    pub fn try_read_le<T: TryEndian>(&mut self) -> Result<T, EndianError> {
+        let mut reader = self.clone();
+        let bytes = reader.read_bytes(T::SIZE)?;
+
+        // The slice returned by read_bytes is exactly T::SIZE bytes long
+        let value = unsafe { T::try_read_le_unchecked(bytes.as_ptr()) }.map_err(|e| e.offset_by(self.pos))?;
+
+        *self = reader;
+        Ok(value)
+    }
+
+    /// Reads a validated value from its big endian bytes
+    /// 
+    /// # Returns
+    /// See [`try_read`](SliceReader::try_read)
+    pub fn try_read_be<T: TryEndian>(&mut self) -> Result<T, EndianError> {
+        let mut reader = self.clone();
+        let bytes = reader.read_bytes(T::SIZE)?;
+
+        // The slice returned by read_bytes is exactly T::SIZE bytes long
+        let value = unsafe { T::try_read_be_unchecked(bytes.as_ptr()) }.map_err(|e| e.offset_by(self.pos))?;
+
+        *self = reader;
+        Ok(value)
+    }
+
+    /// Reads an enum stored as its tag and the payload of its variant in the configured byte order, see
+    /// [`Tagged`]
+    /// 
+    /// # Returns
+    /// Ok - The value, the reader is advanced past its payload only
+    /// Err - [`EndianError::InvalidValue`] when the tag does not match any variant, or when the slice ends
+    /// early. The position is left unchanged on failure
+    pub fn read_tagged<T: Tagged>(&mut self) -> Result<T, EndianError> {
+        self.take_tagged(self.order)
+    }
+
+    /// Reads an enum stored as its little endian tag and payload
+    /// 
+    /// # Returns
+    /// See [`read_tagged`](SliceReader::read_tagged)
+    pub fn read_tagged_le<T: Tagged>(&mut self) -> Result<T, EndianError> {
+        self.take_tagged(LittleEndian)
+    }
+
+    /// Reads an enum stored as its big endian tag and payload
+    /// 
+    /// # Returns
+    /// See [`read_tagged`](SliceReader::read_tagged)
+    pub fn read_tagged_be<T: Tagged>(&mut self) -> Result<T, EndianError> {
+        self.take_tagged(BigEndian)
+    }
+
+    /// Reads a little endian unsigned integer stored in `nbytes` bytes (e.g. 3 for a `u24`)
+    /// 
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    pub fn read_uint_le(&mut self, nbytes: usize) -> Result<u64, EndianError> {
+        uint::check_width(nbytes);
+        self.read_bytes(nbytes).map(|bytes| uint::decode_uint(bytes, false))
+    }
+
+    /// Reads a big endian unsigned integer stored in `nbytes` bytes (e.g. 3 for a `u24`)
+    /// 
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    pub fn read_uint_be(&mut self, nbytes: usize) -> Result<u64, EndianError> {
+        uint::check_width(nbytes);
+        self.read_bytes(nbytes).map(|bytes| uint::decode_uint(bytes, true))
+    }
+
+    /// Reads a little endian two's complement integer stored in `nbytes` bytes and sign extends it
+    /// 
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    pub fn read_int_le(&mut self, nbytes: usize) -> Result<i64, EndianError> {
+        uint::check_width(nbytes);
+        self.read_bytes(nbytes).map(|bytes| uint::decode_int(bytes, false))
+    }
+
+    /// Reads a big endian two's complement integer stored in `nbytes` bytes and sign extends it
+    /// 
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    pub fn read_int_be(&mut self, nbytes: usize) -> Result<i64, EndianError> {
+        uint::check_width(nbytes);
+        self.read_bytes(nbytes).map(|bytes| uint::decode_int(bytes, true))
+    }
+
+    /// Reads a variable length integer in encoding `E` (e.g. [`Leb128`](crate::Leb128))
+    pub fn read_varint<E: Varint>(&mut self) -> Result<E::Value, EndianError> {
+        let remaining = self.remaining();
+        let (value, len) = varint::decode::<E, _>(|i| remaining.get(i).copied().ok_or(EndianError::EndOfStream(i)))
+            .map_err(|e| e.offset_by(self.pos))?;
+
+        self.pos += len;
+        Ok(value)
+    }
+
+    /// Reads a `usize` stored as a `T` (e.g. `u32` or `u64`) in the configured byte order
+    /// 
+    /// # Remarks
+    /// See [`Endian`](crate::Endian) for why `usize` is stored as a fixed width integer
+    /// 
+    /// # Returns
+    /// Ok - The value
+    /// Err - [`EndianError::Overflow`] when the value does not fit in a `usize` on this target, or when the
+    /// slice ends early
+    pub fn read_usize_as<T>(&mut self) -> Result<usize, EndianError>
+        where T: Endian + TryInto<usize>
+    {
+        let mut reader = self.clone();
+        let value = uint::convert(reader.read::<T>()?).map_err(|e| e.offset_by(self.pos))?;
+
+        *self = reader;
+        Ok(value)
+    }
+
+    /// Reads an `isize` stored as a `T` (e.g. `i32` or `i64`) in the configured byte order
+    /// 
+    /// # Returns
+    /// See [`read_usize_as`](SliceReader::read_usize_as)
+    pub fn read_isize_as<T>(&mut self) -> Result<isize, EndianError>
+        where T: Endian + TryInto<isize>
+    {
+        let mut reader = self.clone();
+        let value = uint::convert(reader.read::<T>()?).map_err(|e| e.offset_by(self.pos))?;
+
+        *self = reader;
+        Ok(value)
+    }
+
+    /// Reads a byte string preceded by its length as an `L` in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The bytes following the prefix, borrowed from the slice
+    /// Err - [`EndianError::TooLong`] when the prefix exceeds `max_len`, or when the slice ends early
+    pub fn read_bytes_prefixed<L>(&mut self, max_len: usize) -> Result<&'a [u8], EndianError>
+        where L: Endian + TryInto<usize>
+    {
+        let mut reader = self.clone();
+        let len = string::decode_len(reader.read::<L>()?, max_len).map_err(|e| e.offset_by(self.pos))?;
+        let bytes = reader.read_bytes(len)?;
+
+        *self = reader;
+        Ok(bytes)
+    }
+
+    /// Reads a UTF-8 string preceded by its length in bytes as an `L` in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The string following the prefix, borrowed from the slice
+    /// Err - [`EndianError::TooLong`] when the prefix exceeds `max_len`, [`EndianError::InvalidUtf8`] when
+    /// the string is not valid UTF-8, or when the slice ends early
+    pub fn read_str_prefixed<L>(&mut self, max_len: usize) -> Result<&'a str, EndianError>
+        where L: Endian + TryInto<usize>
+    {
+        let mut reader = self.clone();
+        let bytes = reader.read_bytes_prefixed::<L>(max_len)?;
+        let value = string::decode_utf8(bytes).map_err(|e| e.offset_by(self.pos + L::SIZE))?;
+
+        *self = reader;
+        Ok(value)
+    }
+
+    /// Reads a NUL terminated C string, consuming the terminator
+    /// 
+    /// # Returns
+    /// Ok - The string, borrowed from the slice
+    /// Err - [`EndianError::TooLong`] when no terminator follows the first `max_len` bytes, or when the
+    /// slice ends before a terminator
+    pub fn read_cstr(&mut self, max_len: usize) -> Result<&'a CStr, EndianError> {
+        match string::find_cstr(self.remaining(), max_len) {
+            Ok(Some(value)) => {
+                self.pos += value.to_bytes_with_nul().len();
+                Ok(value)
+            },
+            Ok(None) => Err(EndianError::EndOfStream(self.pos)),
+            Err(e) => Err(e.offset_by(self.pos))
+        }
+    }
+
+    /// Reads a NUL terminated byte string, consuming the terminator
+    /// 
+    /// # Returns
+    /// Ok - The bytes before the terminator, borrowed from the slice
+    /// Err - [`EndianError::TooLong`] when no terminator follows the first `max_len` bytes, or when the
+    /// slice ends before a terminator
+    pub fn read_bytes_nul(&mut self, max_len: usize) -> Result<&'a [u8], EndianError> {
+        self.read_cstr(max_len).map(CStr::to_bytes)
+    }
+
+    /// Reads a fixed width field of `width` bytes and strips the trailing `pad` bytes
+    /// 
+    /// # Remarks
+    /// Values which themselves end in `pad` can not be told apart from the padding and lose those bytes
+    pub fn read_bytes_padded(&mut self, width: usize, pad: u8) -> Result<&'a [u8], EndianError> {
+        self.read_bytes(width).map(|bytes| string::trim_padding(bytes, pad))
+    }
+
+    /// Reads a fixed width UTF-8 field of `width` bytes and strips the trailing `pad` bytes
+    /// 
+    /// # Returns
+    /// Ok - The string without padding, borrowed from the slice
+    /// Err - [`EndianError::InvalidUtf8`] when the string is not valid UTF-8, or when the slice ends early
+    pub fn read_str_padded(&mut self, width: usize, pad: u8) -> Result<&'a str, EndianError> {
+        let mut reader = self.clone();
+        let value = string::decode_utf8(reader.read_bytes_padded(width, pad)?).map_err(|e| e.offset_by(self.pos))?;
+
+        *self = reader;
+        Ok(value)
+    }
+
+    /// Reads UTF-16 or UTF-32 text preceded by its length in code units as an `L`, both in the configured
+    /// byte order
+    /// 
+    /// # Remarks
+    /// A byte order mark at the start of the text overrides the configured byte order for the text and
+    /// is removed
+    /// 
+    /// Available with the `alloc` feature
+    /// 
+    /// # Returns
+    /// Ok - The decoded text
+    /// Err - [`EndianError::TooLong`] when the prefix exceeds `max_len` code units,
+    /// [`EndianError::InvalidUnicode`] when the text contains an unpaired surrogate or invalid code point,
+    /// or when the slice ends early
+    #[cfg(feature = "alloc")]
+    pub fn read_text_prefixed<L>(&mut self, encoding: TextEncoding, max_len: usize) -> Result<String, EndianError>
+        where L: Endian + TryInto<usize>
+    {
+        let mut reader = self.clone();
+        let len = text::byte_len(encoding, reader.read::<L>()?, max_len).map_err(|e| e.offset_by(self.pos))?;
+        let bytes = reader.read_bytes(len)?;
+        let value = text::decode(bytes, encoding, self.order.is_big()).map_err(|e| e.offset_by(self.pos + L::SIZE))?;
+
+        *self = reader;
+        Ok(value)
+    }
+
+    /// Reads UTF-16 or UTF-32 text in the configured byte order terminated by a zero code unit, consuming
+    /// the terminator
+    /// 
+    /// # Remarks
+    /// A byte order mark at the start of the text overrides the configured byte order and is removed
+    /// 
+    /// Available with the `alloc` feature
+    /// 
+    /// # Returns
+    /// Ok - The decoded text
+    /// Err - [`EndianError::TooLong`] when no terminator follows the first `max_len` code units,
+    /// [`EndianError::InvalidUnicode`] when the text contains an unpaired surrogate or invalid code point,
+    /// or when the slice ends before a terminator
+    #[cfg(feature = "alloc")]
+    pub fn read_text_nul(&mut self, encoding: TextEncoding, max_len: usize) -> Result<String, EndianError> {
+        let len = match text::find_nul(self.remaining(), encoding, max_len) {
+            Ok(Some(len)) => len * encoding.unit_size(),
+            Ok(None) => return Err(EndianError::EndOfStream(self.pos)),
+            Err(e) => return Err(e.offset_by(self.pos))
+        };
+
+        let value = text::decode(&self.remaining()[..len], encoding, self.order.is_big())
+            .map_err(|e| e.offset_by(self.pos))?;
+
+        self.pos += len + encoding.unit_size();
+        Ok(value)
+    }
+
+    /// Reads a fixed width field of `width` UTF-16 or UTF-32 code units in the configured byte order and
+    /// strips the trailing zero code units
+    /// 
+    /// # Remarks
+    /// A byte order mark at the start of the text overrides the configured byte order and is removed
+    /// 
+    /// Available with the `alloc` feature
+    /// 
+    /// # Returns
+    /// Ok - The decoded text without padding
+    /// Err - [`EndianError::InvalidUnicode`] when the text contains an unpaired surrogate or invalid code
+    /// point, or when the slice ends early
+    #[cfg(feature = "alloc")]
+    pub fn read_text_padded(&mut self, encoding: TextEncoding, width: usize) -> Result<String, EndianError> {
+        let mut reader = self.clone();
+        let bytes = reader.read_bytes(text::byte_len(encoding, width, width)?)?;
+        let value = text::decode(text::trim_nul(bytes, encoding), encoding, self.order.is_big())
+            .map_err(|e| e.offset_by(self.pos))?;
+
+        *self = reader;
+        Ok(value)
+    }
+
+    /// Reads the next `len` bytes without copying them
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], EndianError> {
+        let remaining = self.remaining();
+        if remaining.len() < len {
+            return Err(EndianError::EndOfStream(self.pos));
+        }
+
+        self.pos += len;
+        Ok(&remaining[..len])
+    }
+
+    /// Advances the reader by `len` bytes
+    pub fn skip(&mut self, len: usize) -> Result<(), EndianError> {
+        self.read_bytes(len).map(|_| ())
+    }
+
+    /// The bytes which have not been read yet
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.buf[self.pos..]
+    }
+
+    /// Returns true when every byte has been read
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.buf.len()
+    }
+
+    /// The number of bytes read so far
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The slice being read
+    pub fn get_ref(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// The byte order used by [`read`](SliceReader::read)
+    pub fn order(&self) -> B {
+        self.order
+    }
+
+    /// Changes the byte order of all later calls to [`read`](SliceReader::read)
+    pub fn set_order(&mut self, order: B) {
+        self.order = order;
+    }
+
+    /// Reads a [`Tagged`] value in `order`, advancing past the bytes of its variant
+    fn take_tagged<T: Tagged, O: ByteOrder>(&mut self, order: O) -> Result<T, EndianError> {
+        let value = T::read_tagged_from(self.remaining(), order).map_err(|e| e.offset_by(self.pos))?;
+        self.pos += value.tagged_len();
+        Ok(value)
+    }
+}
+
+/// A bounded cursor writing [`Endian`] values into a fixed size byte slice
+///
+/// # Remarks
+/// A write which does not fit in the remaining space fails with [`EndianError::EndOfStream`] containing
+/// the number of bytes written so far and leaves the buffer untouched
+/// 
+/// Besides the explicit little and big endian methods the writer stores a byte order used by
+/// [`write`](SliceWriter::write), defaulting to
+/// [`Endianness::Native`]. Using one of the [`ByteOrder`] markers instead fixes the byte order at
+/// compile time
+#[derive(Debug)]
+pub struct SliceWriter<'a, B = Endianness> {
+    buf: &'a mut [u8],
+    pos: usize,
+    order: B
+}
+
+impl<'a> SliceWriter<'a> {
+    /// Creates a writer starting at the beginning of `buf`
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self::with_order(buf, Endianness::Native)
+    }
+}
+
+impl<'a, B: ByteOrder> SliceWriter<'a, B> {
+    /// Creates a writer starting at the beginning of `buf` which writes values in `order`
+    pub fn with_order(buf: &'a mut [u8], order: B) -> Self {
+        SliceWriter {
+            buf,
+            pos: 0,
+            order
+        }
+    }
+
+    /// Writes `value` in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` does not fit in the remaining space
+    pub fn write<T: Endian>(&mut self, value: &T) -> Result<usize, EndianError> {
+        if self.order.is_big() {
+            self.write_be(value)
+        } else {
+            self.write_le(value)
+        }
+    }
+
+    /// Writes the little endian representation of `value`
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` does not fit in the remaining space
+    pub fn write_le<T: Endian>(&mut self, value: &T) -> Result<usize, EndianError> {
+        self.put(value, LittleEndian)
+    }
+
+    /// Writes the big endian representation of `value`
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` does not fit in the remaining space
+    pub fn write_be<T: Endian>(&mut self, value: &T) -> Result<usize, EndianError> {
+        self.put(value, BigEndian)
+    }
+
+    /// Writes a validated value such as a `bool` or `char` in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` does not fit in the remaining space
+    pub fn try_write<T: TryEndian>(&mut self, value: &T) -> Result<usize, EndianError> {
+        self.put_valid(value, self.order)
+    }
+
+    /// Writes the little endian representation of a validated value
+    /// 
+    /// # Returns
+    /// See [`try_write`](SliceWriter::try_write)
+    pub fn try_write_le<T: TryEndian>(&mut self, value: &T) -> Result<usize, EndianError> {
+        self.put_valid(value, LittleEndian)
+    }
+
+    /// Writes the big endian representation of a validated value
+    /// 
+    /// # Returns
+    /// See [`try_write`](SliceWriter::try_write)
+    pub fn try_write_be<T: TryEndian>(&mut self, value: &T) -> Result<usize, EndianError> {
+        self.put_valid(value, BigEndian)
+    }
+
+    /// Writes an enum as its tag and the payload of its variant in the configured byte order, see
+    /// [`Tagged`]
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` does not fit in the remaining space
+    pub fn write_tagged<T: Tagged>(&mut self, value: &T) -> Result<usize, EndianError> {
+        self.put_tagged(value, self.order)
+    }
+
+    /// Writes an enum as its little endian tag and payload
+    /// 
+    /// # Returns
+    /// See [`write_tagged`](SliceWriter::write_tagged)
+    pub fn write_tagged_le<T: Tagged>(&mut self, value: &T) -> Result<usize, EndianError> {
+        self.put_tagged(value, LittleEndian)
+    }
+
+    /// Writes an enum as its big endian tag and payload
+    /// 
+    /// # Returns
+    /// See [`write_tagged`](SliceWriter::write_tagged)
+    pub fn write_tagged_be<T: Tagged>(&mut self, value: &T) -> Result<usize, EndianError> {
+        self.put_tagged(value, BigEndian)
+    }
+
+    /// Writes `value` as a little endian unsigned integer of `nbytes` bytes
+    /// 
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` does not fit in `nbytes` bytes or the remaining space
+    pub fn write_uint_le(&mut self, value: u64, nbytes: usize) -> Result<usize, EndianError> {
+        self.put_uint(value, nbytes, LittleEndian)
+    }
+
+    /// Writes `value` as a big endian unsigned integer of `nbytes` bytes
+    /// 
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` does not fit in `nbytes` bytes or the remaining space
+    pub fn write_uint_be(&mut self, value: u64, nbytes: usize) -> Result<usize, EndianError> {
+        self.put_uint(value, nbytes, BigEndian)
+    }
+
+    /// Writes `value` as a little endian two's complement integer of `nbytes` bytes
+    /// 
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` does not fit in `nbytes` bytes or the remaining space
+    pub fn write_int_le(&mut self, value: i64, nbytes: usize) -> Result<usize, EndianError> {
+        self.put_int(value, nbytes, LittleEndian)
+    }
+
+    /// Writes `value` as a big endian two's complement integer of `nbytes` bytes
+    /// 
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` does not fit in `nbytes` bytes or the remaining space
+    pub fn write_int_be(&mut self, value: i64, nbytes: usize) -> Result<usize, EndianError> {
+        self.put_int(value, nbytes, BigEndian)
+    }
+
+    /// Writes `value` as a variable length integer in encoding `E`
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` can not be represented by the encoding or does not fit in the remaining space
+    pub fn write_varint<E: Varint>(&mut self, value: E::Value) -> Result<usize, EndianError> {
+        self.put_varint::<E>(value)
+    }
+
+    /// Writes `value` as a `T` (e.g. `u32` or `u64`) in the configured byte order
+    /// 
+    /// # Remarks
+    /// See [`Endian`](crate::Endian) for why `usize` is stored as a fixed width integer
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - [`EndianError::Overflow`] when `value` does not fit in a `T`, or when it does not fit in the
+    /// remaining space
+    pub fn write_usize_as<T>(&mut self, value: usize) -> Result<usize, EndianError>
+        where T: Endian + TryFrom<usize>
+    {
+        self.put_as::<T, _>(value)
+    }
+
+    /// Writes `value` as a `T` (e.g. `i32` or `i64`) in the configured byte order
+    /// 
+    /// # Returns
+    /// See [`write_usize_as`](SliceWriter::write_usize_as)
+    pub fn write_isize_as<T>(&mut self, value: isize) -> Result<usize, EndianError>
+        where T: Endian + TryFrom<isize>
+    {
+        self.put_as::<T, _>(value)
+    }
+
+    /// Writes `bytes` preceded by their length as an `L` in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written, including the prefix
+    /// Err - [`EndianError::Overflow`] when the length does not fit in an `L`, or when the prefix and
+    /// bytes do not fit in the remaining space
+    pub fn write_bytes_prefixed<L>(&mut self, bytes: &[u8]) -> Result<usize, EndianError>
+        where L: Endian + TryFrom<usize>
+    {
+        self.put_prefixed::<L>(bytes)
+    }
+
+    /// Writes the UTF-8 bytes of `value` preceded by their length as an `L` in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written, including the prefix
+    /// Err - [`EndianError::Overflow`] when the length does not fit in an `L`, or when the prefix and
+    /// string do not fit in the remaining space
+    pub fn write_str_prefixed<L>(&mut self, value: &str) -> Result<usize, EndianError>
+        where L: Endian + TryFrom<usize>
+    {
+        self.write_bytes_prefixed::<L>(value.as_bytes())
+    }
+
+    /// Writes `value` including its NUL terminator
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written, including the terminator
+    /// Err - When `value` does not fit in the remaining space
+    pub fn write_cstr(&mut self, value: &CStr) -> Result<usize, EndianError> {
+        self.write_bytes(value.to_bytes_with_nul())
+    }
+
+    /// Writes `bytes` as a fixed width field of `width` bytes, filling the rest with `pad`
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written, which is always `width`
+    /// Err - [`EndianError::TooLong`] when `bytes` is longer than `width`, or when the field does not fit
+    /// in the remaining space
+    pub fn write_bytes_padded(&mut self, bytes: &[u8], width: usize, pad: u8) -> Result<usize, EndianError> {
+        self.put_padded(bytes, width, pad)
+    }
+
+    /// Writes the UTF-8 bytes of `value` as a fixed width field of `width` bytes, filling the rest with
+    /// `pad`
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written, which is always `width`
+    /// Err - [`EndianError::TooLong`] when `value` is longer than `width` bytes, or when the field does
+    /// not fit in the remaining space
+    pub fn write_str_padded(&mut self, value: &str, width: usize, pad: u8) -> Result<usize, EndianError> {
+        self.write_bytes_padded(value.as_bytes(), width, pad)
+    }
+
+    /// Writes `value` as UTF-16 or UTF-32 text preceded by its length in code units as an `L`, both in the
+    /// configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written, including the prefix
+    /// Err - [`EndianError::Overflow`] when the length does not fit in an `L`, or when the prefix and text
+    /// do not fit in the remaining space
+    pub fn write_text_prefixed<L>(&mut self, encoding: TextEncoding, value: &str) -> Result<usize, EndianError>
+        where L: Endian + TryFrom<usize>
+    {
+        self.put_text_prefixed::<L>(encoding, value)
+    }
+
+    /// Writes `value` as UTF-16 or UTF-32 text in the configured byte order followed by a zero code unit
+    /// 
+    /// # Remarks
+    /// A NUL character in `value` ends the text early when it is read back
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written, including the terminator
+    /// Err - When the text does not fit in the remaining space
+    pub fn write_text_nul(&mut self, encoding: TextEncoding, value: &str) -> Result<usize, EndianError> {
+        self.put_text_nul(encoding, value)
+    }
+
+    /// Writes `value` as a fixed width field of `width` UTF-16 or UTF-32 code units in the configured byte
+    /// order, filling the rest with zero code units
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - [`EndianError::TooLong`] when `value` needs more than `width` code units, or when the field
+    /// does not fit in the remaining space
+    pub fn write_text_padded(&mut self, encoding: TextEncoding, value: &str, width: usize) -> Result<usize, EndianError> {
+        self.put_text_padded(encoding, value, width)
+    }
+
+    /// Copies `bytes` into the buffer as is
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<usize, EndianError> {
+        self.put_bytes(bytes)
+    }
+
+    /// Advances the writer by `len` bytes, leaving their contents unchanged
+    pub fn skip(&mut self, len: usize) -> Result<(), EndianError> {
+        self.take(len).map(|_| ())
+    }
+
+    /// Reserves the next `len` bytes and returns a writer over them using the same byte order
+    /// 
+    /// # Remarks
+    /// Useful for reserving a header area which is filled in once the following data has been written
+    pub fn split_off(&mut self, len: usize) -> Result<SliceWriter<'a, B>, EndianError> {
+        let order = self.order;
+        self.take(len).map(|buf| SliceWriter::with_order(buf, order))
+    }
+
+    /// The space which has not been written yet
+    /// 
+    /// # Remarks
+    /// Bytes filled in through this slice are not counted as written until the writer is advanced
+    /// past them with [`skip`](SliceWriter::skip)
+    pub fn remaining_mut(&mut self) -> &mut [u8] {
+        self.buf
+    }
+
+    /// The number of bytes which can still be written
+    pub fn remaining_len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// The number of bytes written so far
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The byte order used by [`write`](SliceWriter::write)
+    pub fn order(&self) -> B {
+        self.order
+    }
+
+    /// Changes the byte order of all later calls to [`write`](SliceWriter::write)
+    pub fn set_order(&mut self, order: B) {
+        self.order = order;
+    }
+
+    /// Takes the next `len` bytes out of the unwritten space
+    fn take(&mut self, len: usize) -> Result<&'a mut [u8], EndianError> {
+        if self.buf.len() < len {
+            return Err(EndianError::EndOfStream(self.pos));
+        }
+
+        let (head, tail) = mem::take(&mut self.buf).split_at_mut(len);
+        self.buf = tail;
+        self.pos += len;
+        Ok(head)
+    }
+}
+
+impl<B: ByteOrder> Reserve for SliceWriter<'_, B> {
+    type Order = B;
+
+    fn order(&self) -> B {
+        self.order
+    }
+
+    fn written(&self) -> usize {
+        self.pos
+    }
+
+    fn reserve(&mut self, len: usize) -> Result<&mut [u8], EndianError> {
+        self.take(len)
+    }
+}
+
+/// A writer appending [`Endian`] values to a growable `Vec`
+/// 
+/// Besides the explicit little and big endian methods the writer stores a byte order used by
+/// [`write`](VecWriter::write), defaulting to
+/// [`Endianness::Native`]. Using one of the [`ByteOrder`] markers instead fixes the byte order at
+/// compile time
+/// 
+/// Available with the `alloc` feature
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub struct VecWriter<B = Endianness> {
+    buf: Vec<u8>,
+    order: B
+}
+
+#[cfg(feature = "alloc")]
+impl VecWriter {
+    /// Creates an empty writer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty writer with space for at least `capacity` bytes
+    pub fn with_capacity(capacity: usize) -> Self {
+        VecWriter {
+            buf: Vec::with_capacity(capacity),
+            order: Endianness::Native
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<B: ByteOrder> VecWriter<B> {
+    /// Creates an empty writer which writes values in `order`
+    pub fn with_order(order: B) -> Self {
+        VecWriter {
+            buf: Vec::new(),
+            order
+        }
+    }
+
+    /// Appends `value` in the configured byte order
+    /// 
+    /// # Returns
+    /// The number of bytes written
+    pub fn write<T: Endian>(&mut self, value: &T) -> usize {
+        if self.order.is_big() {
+            self.write_be(value)
+        } else {
+            self.write_le(value)
+        }
+    }
+
+    /// Appends the little endian representation of `value`
+    /// 
+    /// # Returns
+    /// The number of bytes written
+    pub fn write_le<T: Endian>(&mut self, value: &T) -> usize {
+        infallible(self.put(value, LittleEndian))
+    }
+
+    /// Appends the big endian representation of `value`
+    /// 
+    /// # Returns
+    /// The number of bytes written
+    pub fn write_be<T: Endian>(&mut self, value: &T) -> usize {
+        infallible(self.put(value, BigEndian))
+    }
+
+    /// Appends a validated value such as a `bool` or `char` in the configured byte order
+    /// 
+    /// # Returns
+    /// The number of bytes written
+    pub fn try_write<T: TryEndian>(&mut self, value: &T) -> usize {
+        infallible(self.put_valid(value, self.order))
+    }
+
+    /// Appends the little endian representation of a validated value
+    /// 
+    /// # Returns
+    /// The number of bytes written
+    pub fn try_write_le<T: TryEndian>(&mut self, value: &T) -> usize {
+        infallible(self.put_valid(value, LittleEndian))
+    }
+
+    /// Appends the big endian representation of a validated value
+    /// 
+    /// # Returns
+    /// The number of bytes written
+    pub fn try_write_be<T: TryEndian>(&mut self, value: &T) -> usize {
+        infallible(self.put_valid(value, BigEndian))
+    }
+
+    /// Appends an enum as its tag and the payload of its variant in the configured byte order, see
+    /// [`Tagged`]
+    /// 
+    /// # Returns
+    /// The number of bytes written
+    pub fn write_tagged<T: Tagged>(&mut self, value: &T) -> usize {
+        infallible(self.put_tagged(value, self.order))
+    }
+
+    /// Appends an enum as its little endian tag and payload
+    /// 
+    /// # Returns
+    /// The number of bytes written
+    pub fn write_tagged_le<T: Tagged>(&mut self, value: &T) -> usize {
+        infallible(self.put_tagged(value, LittleEndian))
+    }
+
+    /// Appends an enum as its big endian tag and payload
+    /// 
+    /// # Returns
+    /// The number of bytes written
+    pub fn write_tagged_be<T: Tagged>(&mut self, value: &T) -> usize {
+        infallible(self.put_tagged(value, BigEndian))
+    }
+
+    /// Appends `value` as a little endian unsigned integer of `nbytes` bytes
+    /// 
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` does not fit in `nbytes` bytes
+    pub fn write_uint_le(&mut self, value: u64, nbytes: usize) -> Result<usize, EndianError> {
+        self.put_uint(value, nbytes, LittleEndian)
+    }
+
+    /// Appends `value` as a big endian unsigned integer of `nbytes` bytes
+    /// 
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` does not fit in `nbytes` bytes
+    pub fn write_uint_be(&mut self, value: u64, nbytes: usize) -> Result<usize, EndianError> {
+        self.put_uint(value, nbytes, BigEndian)
+    }
+
+    /// Appends `value` as a little endian two's complement integer of `nbytes` bytes
+    /// 
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` does not fit in `nbytes` bytes
+    pub fn write_int_le(&mut self, value: i64, nbytes: usize) -> Result<usize, EndianError> {
+        self.put_int(value, nbytes, LittleEndian)
+    }
+
+    /// Appends `value` as a big endian two's complement integer of `nbytes` bytes
+    /// 
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` does not fit in `nbytes` bytes
+    pub fn write_int_be(&mut self, value: i64, nbytes: usize) -> Result<usize, EndianError> {
+        self.put_int(value, nbytes, BigEndian)
+    }
+
+    /// Appends `value` as a variable length integer in encoding `E`
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` can not be represented by the encoding
+    pub fn write_varint<E: Varint>(&mut self, value: E::Value) -> Result<usize, EndianError> {
+        self.put_varint::<E>(value)
+    }
+
+    /// Appends `value` as a `T` (e.g. `u32` or `u64`) in the configured byte order
+    /// 
+    /// # Remarks
+    /// See [`Endian`](crate::Endian) for why `usize` is stored as a fixed width integer
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - [`EndianError::Overflow`] when `value` does not fit in a `T`
+    pub fn write_usize_as<T>(&mut self, value: usize) -> Result<usize, EndianError>
+        where T: Endian + TryFrom<usize>
+    {
+        self.put_as::<T, _>(value)
+    }
+
+    /// Appends `value` as a `T` (e.g. `i32` or `i64`) in the configured byte order
+    /// 
+    /// # Returns
+    /// See [`write_usize_as`](VecWriter::write_usize_as)
+    pub fn write_isize_as<T>(&mut self, value: isize) -> Result<usize, EndianError>
+        where T: Endian + TryFrom<isize>
+    {
+        self.put_as::<T, _>(value)
+    }
+
+    /// Appends `bytes` preceded by their length as an `L` in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written, including the prefix
+    /// Err - [`EndianError::Overflow`] when the length does not fit in an `L`
+    pub fn write_bytes_prefixed<L>(&mut self, bytes: &[u8]) -> Result<usize, EndianError>
+        where L: Endian + TryFrom<usize>
+    {
+        self.put_prefixed::<L>(bytes)
+    }
+
+    /// Appends the UTF-8 bytes of `value` preceded by their length as an `L` in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written, including the prefix
+    /// Err - [`EndianError::Overflow`] when the length does not fit in an `L`
+    pub fn write_str_prefixed<L>(&mut self, value: &str) -> Result<usize, EndianError>
+        where L: Endian + TryFrom<usize>
+    {
+        self.write_bytes_prefixed::<L>(value.as_bytes())
+    }
+
+    /// Appends `value` including its NUL terminator
+    /// 
+    /// # Returns
+    /// The number of bytes written, including the terminator
+    pub fn write_cstr(&mut self, value: &CStr) -> usize {
+        infallible(self.put_bytes(value.to_bytes_with_nul()))
+    }
+
+    /// Appends `bytes` as a fixed width field of `width` bytes, filling the rest with `pad`
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written, which is always `width`
+    /// Err - [`EndianError::TooLong`] when `bytes` is longer than `width`
+    pub fn write_bytes_padded(&mut self, bytes: &[u8], width: usize, pad: u8) -> Result<usize, EndianError> {
+        self.put_padded(bytes, width, pad)
+    }
+
+    /// Appends the UTF-8 bytes of `value` as a fixed width field of `width` bytes, filling the rest with
+    /// `pad`
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written, which is always `width`
+    /// Err - [`EndianError::TooLong`] when `value` is longer than `width` bytes
+    pub fn write_str_padded(&mut self, value: &str, width: usize, pad: u8) -> Result<usize, EndianError> {
+        self.write_bytes_padded(value.as_bytes(), width, pad)
+    }
+
+    /// Appends `value` as UTF-16 or UTF-32 text preceded by its length in code units as an `L`, both in
+    /// the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written, including the prefix
+    /// Err - [`EndianError::Overflow`] when the length does not fit in an `L`
+    pub fn write_text_prefixed<L>(&mut self, encoding: TextEncoding, value: &str) -> Result<usize, EndianError>
+        where L: Endian + TryFrom<usize>
+    {
+        self.put_text_prefixed::<L>(encoding, value)
+    }
+
+    /// Appends `value` as UTF-16 or UTF-32 text in the configured byte order followed by a zero code unit
+    /// 
+    /// # Remarks
+    /// A NUL character in `value` ends the text early when it is read back
+    /// 
+    /// # Returns
+    /// The number of bytes written, including the terminator
+    pub fn write_text_nul(&mut self, encoding: TextEncoding, value: &str) -> usize {
+        infallible(self.put_text_nul(encoding, value))
+    }
+
+    /// Appends `value` as a fixed width field of `width` UTF-16 or UTF-32 code units in the configured
+    /// byte order, filling the rest with zero code units
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - [`EndianError::TooLong`] when `value` needs more than `width` code units
+    pub fn write_text_padded(&mut self, encoding: TextEncoding, value: &str, width: usize) -> Result<usize, EndianError> {
+        self.put_text_padded(encoding, value, width)
+    }
+
+    /// Appends `bytes` as is
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> usize {
+        infallible(self.put_bytes(bytes))
+    }
+
+    /// The number of bytes written so far
+    pub fn position(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// The byte order used by [`write`](VecWriter::write)
+    pub fn order(&self) -> B {
+        self.order
+    }
+
+    /// Changes the byte order of all later calls to [`write`](VecWriter::write)
+    pub fn set_order(&mut self, order: B) {
+        self.order = order;
+    }
+
+    /// The bytes written so far
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Consumes the writer, returning the underlying `Vec`
+    pub fn into_inner(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<B: ByteOrder> Reserve for VecWriter<B> {
+    type Order = B;
+
+    fn order(&self) -> B {
+        self.order
+    }
+
+    fn written(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Grows the buffer by `len` zeroed bytes and returns them
+    fn reserve(&mut self, len: usize) -> Result<&mut [u8], EndianError> {
+        let start = self.buf.len();
+        self.buf.resize(start + len, 0);
+        Ok(&mut self.buf[start..])
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Vec<u8>> for VecWriter {
+    /// Creates a writer appending to the end of `buf`
+    fn from(buf: Vec<u8>) -> Self {
+        VecWriter {
+            buf,
+            order: Endianness::Native
+        }
+    }
+}
+
+/// Unwraps the result of a [`Reserve`] method which can only fail by running out of space, which
+/// appending to a `Vec` never does
+#[cfg(feature = "alloc")]
+fn infallible(result: Result<usize, EndianError>) -> usize {
+    match result {
+        Ok(len) => len,
+        Err(_) => unreachable!("appending to a Vec can not run out of space")
+    }
+}
+
+/// The space a writer encodes into, shared by [`SliceWriter`] and `VecWriter`
+///
+/// # Remarks
+/// The provided methods hold the encoding of every writer method, the writers only differ in how
+/// space is reserved and whether running out of it is an error
+trait Reserve {
+    /// The byte order used by the order aware methods
+    type Order: ByteOrder;
+
+    /// The byte order used by the order aware methods
+    fn order(&self) -> Self::Order;
+
+    /// The number of bytes written so far, which encoding errors are offset by
+    fn written(&self) -> usize;
+
+    /// Takes the next `len` bytes, failing without writing anything when they do not fit
+    fn reserve(&mut self, len: usize) -> Result<&mut [u8], EndianError>;
+
+    /// Writes `value` in `order`
+    fn put<T: Endian, O: ByteOrder>(&mut self, value: &T, order: O) -> Result<usize, EndianError> {
+        let out = self.reserve(T::SIZE)?;
+
+        // The slice returned by reserve is exactly T::SIZE bytes long
+        Ok(unsafe {
+            if order.is_big() {
+                value.write_be_unchecked(out.as_mut_ptr())
+            } else {
+                value.write_le_unchecked(out.as_mut_ptr())
+            }
+        })
+    }
+
+    /// Writes the validated `value` in `order`
+    fn put_valid<T: TryEndian, O: ByteOrder>(&mut self, value: &T, order: O) -> Result<usize, EndianError> {
+        let out = self.reserve(T::SIZE)?;
+
+        // The slice returned by reserve is exactly T::SIZE bytes long
+        Ok(unsafe {
+            if order.is_big() {
+                value.write_be_unchecked(out.as_mut_ptr())
+            } else {
+                value.write_le_unchecked(out.as_mut_ptr())
+            }
+        })
+    }
+
+    /// Writes the tag and payload of `value` in `order`
+    fn put_tagged<T: Tagged, O: ByteOrder>(&mut self, value: &T, order: O) -> Result<usize, EndianError> {
+        let out = self.reserve(value.tagged_len())?;
+        value.write_tagged_into(out, order)
+    }
+
+    /// Writes `value` as an unsigned integer of `nbytes` bytes in `order`
+    fn put_uint<O: ByteOrder>(&mut self, value: u64, nbytes: usize, order: O) -> Result<usize, EndianError> {
+        let buf = uint::encode_uint(value, nbytes, order.is_big()).map_err(|e| e.offset_by(self.written()))?;
+        self.put_bytes(&buf[..nbytes])
+    }
+
+    /// Writes `value` as a two's complement integer of `nbytes` bytes in `order`
+    fn put_int<O: ByteOrder>(&mut self, value: i64, nbytes: usize, order: O) -> Result<usize, EndianError> {
+        let buf = uint::encode_int(value, nbytes, order.is_big()).map_err(|e| e.offset_by(self.written()))?;
+        self.put_bytes(&buf[..nbytes])
+    }
+
+    /// Writes `value` as a variable length integer in encoding `E`
+    fn put_varint<E: Varint>(&mut self, value: E::Value) -> Result<usize, EndianError> {
+        let (buf, len) = varint::encode::<E>(value).map_err(|e| e.offset_by(self.written()))?;
+        self.put_bytes(&buf[..len])
+    }
+
+    /// Writes `value` converted to a `T` in the configured byte order
+    fn put_as<T, V>(&mut self, value: V) -> Result<usize, EndianError>
+        where T: Endian + TryFrom<V>
+    {
+        let value = uint::convert::<_, T>(value).map_err(|e| e.offset_by(self.written()))?;
+        self.put(&value, self.order())
+    }
+
+    /// Writes `bytes` preceded by their length as an `L` in the configured byte order
+    fn put_prefixed<L>(&mut self, bytes: &[u8]) -> Result<usize, EndianError>
+        where L: Endian + TryFrom<usize>
+    {
+        let len = string::encode_len::<L>(bytes.len()).map_err(|e| e.offset_by(self.written()))?;
+        let order = self.order();
+        let out = self.reserve(L::SIZE + bytes.len())?;
+        let (prefix, value) = out.split_at_mut(L::SIZE);
+        len.write_into(prefix, order)?;
+        value.copy_from_slice(bytes);
+        Ok(out.len())
+    }
+
+    /// Writes `bytes` as a field of `width` bytes, filling the rest with `pad`
+    fn put_padded(&mut self, bytes: &[u8], width: usize, pad: u8) -> Result<usize, EndianError> {
+        string::check_width(bytes.len(), width).map_err(|e| e.offset_by(self.written()))?;
+
+        let out = self.reserve(width)?;
+        let (value, padding) = out.split_at_mut(bytes.len());
+        value.copy_from_slice(bytes);
+        padding.fill(pad);
+        Ok(width)
+    }
+
+    /// Writes `value` as text preceded by its length in code units as an `L`, both in the configured
+    /// byte order
+    fn put_text_prefixed<L>(&mut self, encoding: TextEncoding, value: &str) -> Result<usize, EndianError>
+        where L: Endian + TryFrom<usize>
+    {
+        let units = encoding.encoded_len(value);
+        let len = string::encode_len::<L>(units).map_err(|e| e.offset_by(self.written()))?;
+        let order = self.order();
+        let out = self.reserve(L::SIZE + units * encoding.unit_size())?;
+        let (prefix, text) = out.split_at_mut(L::SIZE);
+        len.write_into(prefix, order)?;
+        text::encode_into(value, encoding, order.is_big(), text);
+        Ok(out.len())
+    }
+
+    /// Writes `value` as text in the configured byte order followed by a zero code unit
+    fn put_text_nul(&mut self, encoding: TextEncoding, value: &str) -> Result<usize, EndianError> {
+        let len = encoding.encoded_len(value) * encoding.unit_size();
+        let order = self.order();
+        let out = self.reserve(len + encoding.unit_size())?;
+        let (units, nul) = out.split_at_mut(len);
+        text::encode_into(value, encoding, order.is_big(), units);
+        nul.fill(0);
+        Ok(out.len())
+    }
+
+    /// Writes `value` as a field of `width` code units in the configured byte order, filling the rest
+    /// with zero code units
+    fn put_text_padded(&mut self, encoding: TextEncoding, value: &str, width: usize) -> Result<usize, EndianError> {
+        let len = text::byte_len(encoding, encoding.encoded_len(value), width).map_err(|e| e.offset_by(self.written()))?;
+        let order = self.order();
+        let out = self.reserve(width * encoding.unit_size())?;
+        let (units, padding) = out.split_at_mut(len);
+        text::encode_into(value, encoding, order.is_big(), units);
+        padding.fill(0);
+        Ok(out.len())
+    }
+
+    /// Copies `bytes` as is
+    fn put_bytes(&mut self, bytes: &[u8]) -> Result<usize, EndianError> {
+        self.reserve(bytes.len())?.copy_from_slice(bytes);
+        Ok(bytes.len())
+    }
+}
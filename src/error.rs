@@ -0,0 +1,232 @@
+//! The error type shared by every conversion in the crate
+
+use core::fmt;
+use core::str::Utf8Error;
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::io;
+
+/// An error incurred when converting between binary representations
+///
+/// # Remarks
+/// The `offset` of each variant is measured in bytes from the start of the operation, which is the
+/// start of the slice for [`SliceReader`](crate::SliceReader) and [`SliceWriter`](crate::SliceWriter),
+/// the start of the vector for `VecWriter` and the position the wrapper was created at for
+/// `EndianReader` and `EndianWriter`. Plain `io` streams do not know their position, so the
+/// [`Endian`](crate::Endian) functions and the `ReadEndianExt`/`WriteEndianExt` methods report offsets
+/// from the start of the call
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EndianError {
+    /// Reached the end of the stream while reading/writing, contains the number of bytes written/read
+    EndOfStream(usize),
+    /// The underlying stream failed while reading/writing a value
+    #[cfg(feature = "std")]
+    Io {
+        /// Offset of the value, see the remarks on [`EndianError`]
+        offset: usize,
+        /// The number of bytes needed by the value
+        expected: usize,
+        /// The number of bytes read/written before the failure
+        actual: usize,
+        /// Name of the type being read/written, empty when unknown
+        type_name: &'static str,
+        /// The error reported by the stream
+        source: io::Error
+    },
+    /// A value does not fit in the type or encoding it is converted to
+    Overflow {
+        /// Offset of the value, see the remarks on [`EndianError`]
+        offset: usize,
+        /// Name of the type or encoding the value did not fit in
+        type_name: &'static str
+    },
+    /// A variable length value was encoded with more bytes than necessary
+    Overlong {
+        /// Offset of the value, see the remarks on [`EndianError`]
+        offset: usize
+    },
+    /// A length prefixed or delimited value is longer than the configured limit
+    TooLong {
+        /// Offset of the value, see the remarks on [`EndianError`]
+        offset: usize,
+        /// The length of the value in bytes, for unterminated values the number of bytes scanned
+        len: usize,
+        /// The largest length allowed in bytes
+        max: usize
+    },
+    /// A string is not valid UTF-8
+    InvalidUtf8 {
+        /// Offset of the string, see the remarks on [`EndianError`]
+        offset: usize,
+        /// The error reported while validating the string
+        source: Utf8Error
+    },
+    /// Text contains an unpaired surrogate or a code unit which is not a valid character
+    InvalidUnicode {
+        /// Offset of the code unit, see the remarks on [`EndianError`]
+        offset: usize,
+        /// Name of the encoding, e.g. `UTF-16`
+        encoding: &'static str,
+        /// The offending code unit
+        unit: u32
+    },
+    /// The bytes of a validated type such as `bool` or `char` do not encode a valid value
+    InvalidValue {
+        /// Offset of the value, see the remarks on [`EndianError`]
+        offset: usize,
+        /// Name of the type being read
+        type_name: &'static str,
+        /// The offending bytes in the order they were read, only the first `len` are used
+        bytes: [u8; 16],
+        /// The number of offending bytes
+        len: usize
+    }
+}
+
+impl EndianError {
+    /// Creates an io error for a value of type `T`
+    #[cfg(feature = "std")]
+    pub(crate) fn io<T: ?Sized>(expected: usize, actual: usize, source: io::Error) -> Self {
+        EndianError::Io {
+            offset: 0,
+            expected,
+            actual,
+            type_name: core::any::type_name::<T>(),
+            source
+        }
+    }
+
+    /// Creates an overflow error for the type or encoding named `type_name`
+    pub(crate) fn overflow(type_name: &'static str) -> Self {
+        EndianError::Overflow { offset: 0, type_name }
+    }
+
+    /// Creates an invalid value error for the bytes of the type named `type_name`, keeping at most the
+    /// first 16
+    pub(crate) fn invalid_value(type_name: &'static str, value: &[u8]) -> Self {
+        let len = value.len().min(16);
+        let mut bytes = [0; 16];
+        bytes[..len].copy_from_slice(&value[..len]);
+        EndianError::InvalidValue {
+            offset: 0,
+            type_name,
+            bytes,
+            len
+        }
+    }
+
+    /// Shifts the error by `offset` bytes
+    ///
+    /// # Remarks
+    /// Used when a value is read/written as part of a larger one so the error reports the position
+    /// relative to the start of the outer value
+    pub fn offset_by(self, offset: usize) -> Self {
+        match self {
+            EndianError::EndOfStream(count) => EndianError::EndOfStream(count + offset),
+            #[cfg(feature = "std")]
+            EndianError::Io { offset: inner, expected, actual, type_name, source } => EndianError::Io {
+                offset: inner + offset,
+                expected,
+                actual,
+                type_name,
+                source
+            },
+            EndianError::Overflow { offset: inner, type_name } => EndianError::Overflow {
+                offset: inner + offset,
+                type_name
+            },
+            EndianError::Overlong { offset: inner } => EndianError::Overlong { offset: inner + offset },
+            EndianError::TooLong { offset: inner, len, max } => EndianError::TooLong {
+                offset: inner + offset,
+                len,
+                max
+            },
+            EndianError::InvalidUtf8 { offset: inner, source } => EndianError::InvalidUtf8 {
+                offset: inner + offset,
+                source
+            },
+            EndianError::InvalidUnicode { offset: inner, encoding, unit } => EndianError::InvalidUnicode {
+                offset: inner + offset,
+                encoding,
+                unit
+            },
+            EndianError::InvalidValue { offset: inner, type_name, bytes, len } => EndianError::InvalidValue {
+                offset: inner + offset,
+                type_name,
+                bytes,
+                len
+            }
+        }
+    }
+}
+
+impl fmt::Display for EndianError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EndianError::EndOfStream(count) => write!(f, "reached the end of the stream after {} bytes", count),
+            #[cfg(feature = "std")]
+            EndianError::Io { type_name: "", source, .. } => write!(f, "{}", source),
+            #[cfg(feature = "std")]
+            EndianError::Io { offset, expected, actual, type_name, source } => write!(
+                f,
+                "failed on `{}` at offset {} after {} of {} bytes: {}",
+                type_name, offset, actual, expected, source
+            ),
+            EndianError::Overflow { offset, type_name } => write!(f, "value at offset {} overflows `{}`", offset, type_name),
+            EndianError::Overlong { offset } => write!(f, "value at offset {} uses an overlong encoding", offset),
+            EndianError::TooLong { offset, len, max } => write!(
+                f,
+                "value at offset {} is {} bytes long, exceeding the limit of {}",
+                offset, len, max
+            ),
+            EndianError::InvalidUtf8 { offset, source } => write!(f, "invalid UTF-8 in string at offset {}: {}", offset, source),
+            EndianError::InvalidUnicode { offset, encoding, unit } => write!(
+                f,
+                "invalid {} code unit {:#x} at offset {}",
+                encoding, unit, offset
+            ),
+            EndianError::InvalidValue { offset, type_name, bytes, len } => write!(
+                f,
+                "invalid `{}` at offset {}: {:02x?}",
+                type_name, offset, &bytes[..*len]
+            )
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for EndianError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EndianError::Io { source, .. } => Some(source),
+            EndianError::InvalidUtf8 { source, .. } => Some(source),
+            _ => None
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for EndianError {
+    fn from(source: io::Error) -> Self {
+        EndianError::Io {
+            offset: 0,
+            expected: 0,
+            actual: 0,
+            type_name: "",
+            source
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<EndianError> for io::Error {
+    fn from(err: EndianError) -> Self {
+        match err {
+            EndianError::EndOfStream(_) => io::Error::new(io::ErrorKind::UnexpectedEof, err),
+            EndianError::Io { ref source, .. } => io::Error::new(source.kind(), err),
+            _ => io::Error::new(io::ErrorKind::InvalidData, err)
+        }
+    }
+}
@@ -0,0 +1,611 @@
+//! Extension traits adding `byteorder` style methods to `io::Read` and `io::Write`
+
+use std::convert::{TryFrom, TryInto};
+use std::ffi::{CStr, CString};
+use std::io::{Read, Write};
+
+use crate::string;
+use crate::text::{self, TextEncoding};
+use crate::uint;
+use crate::varint::{self, Varint};
+use crate::{read_full, write_full, ByteOrder, Endian, EndianError, Tagged, TryEndian};
+
+macro_rules! read_methods {
+    ($($ty:ty => $le:ident, $be:ident;)*) => {
+        $(
+            #[doc = concat!("Reads a little endian `", stringify!($ty), "` from the stream")]
+            #[inline]
+            fn $le(&mut self) -> Result<$ty, EndianError> {
+                <$ty>::read_le(self)
+            }
+
+            #[doc = concat!("Reads a big endian `", stringify!($ty), "` from the stream")]
+            #[inline]
+            fn $be(&mut self) -> Result<$ty, EndianError> {
+                <$ty>::read_be(self)
+            }
+        )*
+    };
+}
+
+macro_rules! write_methods {
+    ($($ty:ty => $le:ident, $be:ident;)*) => {
+        $(
+            #[doc = concat!("Writes `value` to the stream as a little endian `", stringify!($ty), "`")]
+            #[inline]
+            fn $le(&mut self, value: $ty) -> Result<usize, EndianError> {
+                Endian::write_le(&value, self)
+            }
+
+            #[doc = concat!("Writes `value` to the stream as a big endian `", stringify!($ty), "`")]
+            #[inline]
+            fn $be(&mut self, value: $ty) -> Result<usize, EndianError> {
+                Endian::write_be(&value, self)
+            }
+        )*
+    };
+}
+
+/// Extends `io::Read` with methods for reading [`Endian`] values
+///
+/// # Remarks
+/// Implemented for every `io::Read`, each method forwards to the matching [`Endian`] function
+pub trait ReadEndianExt: Read + Sized {
+    /// Reads a `u8` from the stream
+    #[inline]
+    fn read_u8(&mut self) -> Result<u8, EndianError> {
+        u8::read_le(self)
+    }
+
+    /// Reads an `i8` from the stream
+    #[inline]
+    fn read_i8(&mut self) -> Result<i8, EndianError> {
+        i8::read_le(self)
+    }
+
+    read_methods! {
+        u16 => read_u16_le, read_u16_be;
+        u32 => read_u32_le, read_u32_be;
+        u64 => read_u64_le, read_u64_be;
+        u128 => read_u128_le, read_u128_be;
+        i16 => read_i16_le, read_i16_be;
+        i32 => read_i32_le, read_i32_be;
+        i64 => read_i64_le, read_i64_be;
+        i128 => read_i128_le, read_i128_be;
+        f32 => read_f32_le, read_f32_be;
+        f64 => read_f64_le, read_f64_be;
+    }
+
+    /// Reads any little endian [`Endian`] value from the stream
+    #[inline]
+    fn read_le<T: Endian>(&mut self) -> Result<T, EndianError> {
+        T::read_le(self)
+    }
+
+    /// Reads any big endian [`Endian`] value from the stream
+    #[inline]
+    fn read_be<T: Endian>(&mut self) -> Result<T, EndianError> {
+        T::read_be(self)
+    }
+
+    /// Reads any little endian [`TryEndian`] value such as a `bool` or `char` from the stream
+    #[inline]
+    fn try_read_le<T: TryEndian>(&mut self) -> Result<T, EndianError> {
+        T::try_read_le(self)
+    }
+
+    /// Reads any big endian [`TryEndian`] value such as a `bool` or `char` from the stream
+    #[inline]
+    fn try_read_be<T: TryEndian>(&mut self) -> Result<T, EndianError> {
+        T::try_read_be(self)
+    }
+
+    /// Reads an enum stored as its tag and the payload of its variant in `order`, see [`Tagged`]
+    ///
+    /// # Remarks
+    /// Only the bytes of the value are consumed, unlike the padded encoding read by
+    /// [`read_le`](ReadEndianExt::read_le)
+    #[inline]
+    fn read_tagged<T: Tagged>(&mut self, order: impl ByteOrder) -> Result<T, EndianError> {
+        T::read_tagged(self, order)
+    }
+
+    /// Reads a little endian unsigned integer stored in `nbytes` bytes (e.g. 3 for a `u24`)
+    ///
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    fn read_uint_le(&mut self, nbytes: usize) -> Result<u64, EndianError> {
+        read_width::<u64, _>(self, nbytes).map(|buf| uint::decode_uint(&buf[..nbytes], false))
+    }
+
+    /// Reads a big endian unsigned integer stored in `nbytes` bytes (e.g. 3 for a `u24`)
+    ///
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    fn read_uint_be(&mut self, nbytes: usize) -> Result<u64, EndianError> {
+        read_width::<u64, _>(self, nbytes).map(|buf| uint::decode_uint(&buf[..nbytes], true))
+    }
+
+    /// Reads a little endian two's complement integer stored in `nbytes` bytes and sign extends it
+    ///
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    fn read_int_le(&mut self, nbytes: usize) -> Result<i64, EndianError> {
+        read_width::<i64, _>(self, nbytes).map(|buf| uint::decode_int(&buf[..nbytes], false))
+    }
+
+    /// Reads a big endian two's complement integer stored in `nbytes` bytes and sign extends it
+    ///
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    fn read_int_be(&mut self, nbytes: usize) -> Result<i64, EndianError> {
+        read_width::<i64, _>(self, nbytes).map(|buf| uint::decode_int(&buf[..nbytes], true))
+    }
+
+    /// Reads a byte string preceded by its length as an `L` in `order`, e.g. a big endian `u16`
+    ///
+    /// # Remarks
+    /// The prefix is checked against `max_len` before anything is allocated, guarding against corrupt
+    /// or malicious prefixes
+    ///
+    /// # Returns
+    /// Ok - The bytes following the prefix
+    /// Err - [`EndianError::TooLong`] when the prefix exceeds `max_len`, or the stream error
+    fn read_bytes_prefixed<L>(&mut self, order: impl ByteOrder, max_len: usize) -> Result<Vec<u8>, EndianError>
+        where L: Endian + TryInto<usize>
+    {
+        let len = string::decode_len(L::read(self, order)?, max_len)?;
+
+        let mut bytes = vec![0; len];
+        read_full::<[u8], _>(self, &mut bytes).map_err(|e| e.offset_by(L::SIZE))?;
+        Ok(bytes)
+    }
+
+    /// Reads a UTF-8 string preceded by its length in bytes as an `L` in `order`
+    ///
+    /// # Remarks
+    /// The prefix is checked against `max_len` before anything is allocated, guarding against corrupt
+    /// or malicious prefixes
+    ///
+    /// # Returns
+    /// Ok - The string following the prefix
+    /// Err - [`EndianError::TooLong`] when the prefix exceeds `max_len`, [`EndianError::InvalidUtf8`] when
+    /// the string is not valid UTF-8, or the stream error
+    fn read_string_prefixed<L>(&mut self, order: impl ByteOrder, max_len: usize) -> Result<String, EndianError>
+        where L: Endian + TryInto<usize>
+    {
+        let bytes = self.read_bytes_prefixed::<L>(order, max_len)?;
+        String::from_utf8(bytes).map_err(|e| EndianError::InvalidUtf8 { offset: L::SIZE, source: e.utf8_error() })
+    }
+
+    /// Reads a NUL terminated byte string, consuming the terminator
+    ///
+    /// # Remarks
+    /// The stream is read one byte at a time so no bytes past the terminator are consumed
+    ///
+    /// # Returns
+    /// Ok - The bytes before the terminator
+    /// Err - [`EndianError::TooLong`] when no terminator follows the first `max_len` bytes, or the stream
+    /// error
+    fn read_bytes_nul(&mut self, max_len: usize) -> Result<Vec<u8>, EndianError> {
+        let mut bytes = Vec::new();
+        loop {
+            let byte = u8::read_le(self).map_err(|e| e.offset_by(bytes.len()))?;
+            if byte == 0 {
+                return Ok(bytes);
+            }
+
+            if bytes.len() == max_len {
+                return Err(string::too_long_unterminated(max_len));
+            }
+
+            bytes.push(byte);
+        }
+    }
+
+    /// Reads a NUL terminated C string, consuming the terminator
+    ///
+    /// # Returns
+    /// Ok - The string
+    /// Err - [`EndianError::TooLong`] when no terminator follows the first `max_len` bytes, or the stream
+    /// error
+    fn read_cstring(&mut self, max_len: usize) -> Result<CString, EndianError> {
+        let bytes = self.read_bytes_nul(max_len)?;
+
+        // read_bytes_nul stops at the first NUL so bytes contains none
+        Ok(unsafe { CString::from_vec_unchecked(bytes) })
+    }
+
+    /// Reads a fixed width field of `width` bytes and strips the trailing `pad` bytes
+    ///
+    /// # Remarks
+    /// Values which themselves end in `pad` can not be told apart from the padding and lose those bytes
+    fn read_bytes_padded(&mut self, width: usize, pad: u8) -> Result<Vec<u8>, EndianError> {
+        let mut bytes = vec![0; width];
+        read_full::<[u8], _>(self, &mut bytes)?;
+
+        let len = string::trim_padding(&bytes, pad).len();
+        bytes.truncate(len);
+        Ok(bytes)
+    }
+
+    /// Reads a fixed width UTF-8 field of `width` bytes and strips the trailing `pad` bytes
+    ///
+    /// # Returns
+    /// Ok - The string without padding
+    /// Err - [`EndianError::InvalidUtf8`] when the string is not valid UTF-8, or the stream error
+    fn read_string_padded(&mut self, width: usize, pad: u8) -> Result<String, EndianError> {
+        let bytes = self.read_bytes_padded(width, pad)?;
+        String::from_utf8(bytes).map_err(|e| EndianError::InvalidUtf8 { offset: 0, source: e.utf8_error() })
+    }
+
+    /// Reads UTF-16 or UTF-32 text preceded by its length in code units as an `L`, both in `order`
+    ///
+    /// # Remarks
+    /// The prefix is checked against `max_len` code units before anything is allocated. A byte order
+    /// mark at the start of the text overrides `order` for the text and is removed
+    ///
+    /// # Returns
+    /// Ok - The decoded text
+    /// Err - [`EndianError::TooLong`] when the prefix exceeds `max_len`, [`EndianError::InvalidUnicode`]
+    /// when the text contains an unpaired surrogate or invalid code point, or the stream error
+    fn read_text_prefixed<L>(&mut self, encoding: TextEncoding, order: impl ByteOrder, max_len: usize) -> Result<String, EndianError>
+        where L: Endian + TryInto<usize>
+    {
+        let len = text::byte_len(encoding, L::read(self, order)?, max_len)?;
+
+        let mut bytes = vec![0; len];
+        read_full::<[u8], _>(self, &mut bytes).map_err(|e| e.offset_by(L::SIZE))?;
+        text::decode(&bytes, encoding, order.is_big()).map_err(|e| e.offset_by(L::SIZE))
+    }
+
+    /// Reads UTF-16 or UTF-32 text in `order` terminated by a zero code unit, consuming the terminator
+    ///
+    /// # Remarks
+    /// The stream is read one code unit at a time so no bytes past the terminator are consumed. A byte
+    /// order mark at the start of the text overrides `order` and is removed
+    ///
+    /// # Returns
+    /// Ok - The decoded text
+    /// Err - [`EndianError::TooLong`] when no terminator follows the first `max_len` code units,
+    /// [`EndianError::InvalidUnicode`] when the text contains an unpaired surrogate or invalid code point,
+    /// or the stream error
+    fn read_text_nul(&mut self, encoding: TextEncoding, order: impl ByteOrder, max_len: usize) -> Result<String, EndianError> {
+        let size = encoding.unit_size();
+        let mut bytes = Vec::new();
+        let mut unit = [0; 4];
+        loop {
+            read_full::<[u8], _>(self, &mut unit[..size]).map_err(|e| e.offset_by(bytes.len()))?;
+            if unit[..size].iter().all(|&byte| byte == 0) {
+                break;
+            }
+
+            if bytes.len() / size == max_len {
+                return Err(text::too_long(encoding, max_len.saturating_add(1), max_len));
+            }
+
+            bytes.extend_from_slice(&unit[..size]);
+        }
+
+        text::decode(&bytes, encoding, order.is_big())
+    }
+
+    /// Reads a fixed width field of `width` UTF-16 or UTF-32 code units in `order` and strips the
+    /// trailing zero code units
+    ///
+    /// # Remarks
+    /// A byte order mark at the start of the text overrides `order` and is removed
+    ///
+    /// # Returns
+    /// Ok - The decoded text without padding
+    /// Err - [`EndianError::InvalidUnicode`] when the text contains an unpaired surrogate or invalid code
+    /// point, or the stream error
+    fn read_text_padded(&mut self, encoding: TextEncoding, order: impl ByteOrder, width: usize) -> Result<String, EndianError> {
+        let mut bytes = vec![0; text::byte_len(encoding, width, width)?];
+        read_full::<[u8], _>(self, &mut bytes)?;
+        text::decode(text::trim_nul(&bytes, encoding), encoding, order.is_big())
+    }
+
+    /// Reads a variable length integer in encoding `E` (e.g. [`Leb128`](crate::Leb128)) from the stream
+    ///
+    /// # Remarks
+    /// The value is read one byte at a time so no bytes past its end are consumed
+    fn read_varint<E: Varint>(&mut self) -> Result<E::Value, EndianError> {
+        varint::decode::<E, _>(|i| u8::read_le(self).map_err(|e| e.offset_by(i))).map(|(value, _)| value)
+    }
+
+    /// Reads a `usize` stored as a `T` (e.g. `u32` or `u64`) in `order`
+    ///
+    /// # Remarks
+    /// See [`Endian`](crate::Endian) for why `usize` is stored as a fixed width integer
+    ///
+    /// # Returns
+    /// Ok - The value
+    /// Err - [`EndianError::Overflow`] when the value does not fit in a `usize` on this target, or the
+    /// stream error
+    fn read_usize_as<T>(&mut self, order: impl ByteOrder) -> Result<usize, EndianError>
+        where T: Endian + TryInto<usize>
+    {
+        uint::convert(T::read(self, order)?)
+    }
+
+    /// Reads an `isize` stored as a `T` (e.g. `i32` or `i64`) in `order`
+    ///
+    /// # Returns
+    /// See [`read_usize_as`](ReadEndianExt::read_usize_as)
+    fn read_isize_as<T>(&mut self, order: impl ByteOrder) -> Result<isize, EndianError>
+        where T: Endian + TryInto<isize>
+    {
+        uint::convert(T::read(self, order)?)
+    }
+}
+
+impl<R: Read> ReadEndianExt for R { }
+
+/// Extends `io::Write` with methods for writing [`Endian`] values
+///
+/// # Remarks
+/// Implemented for every `io::Write`, each method forwards to the matching [`Endian`] function and
+/// returns the number of bytes written
+pub trait WriteEndianExt: Write + Sized {
+    /// Writes a `u8` to the stream
+    #[inline]
+    fn write_u8(&mut self, value: u8) -> Result<usize, EndianError> {
+        Endian::write_le(&value, self)
+    }
+
+    /// Writes an `i8` to the stream
+    #[inline]
+    fn write_i8(&mut self, value: i8) -> Result<usize, EndianError> {
+        Endian::write_le(&value, self)
+    }
+
+    write_methods! {
+        u16 => write_u16_le, write_u16_be;
+        u32 => write_u32_le, write_u32_be;
+        u64 => write_u64_le, write_u64_be;
+        u128 => write_u128_le, write_u128_be;
+        i16 => write_i16_le, write_i16_be;
+        i32 => write_i32_le, write_i32_be;
+        i64 => write_i64_le, write_i64_be;
+        i128 => write_i128_le, write_i128_be;
+        f32 => write_f32_le, write_f32_be;
+        f64 => write_f64_le, write_f64_be;
+    }
+
+    /// Writes any [`Endian`] value to the stream in little endian representation
+    #[inline]
+    fn write_le<T: Endian>(&mut self, value: &T) -> Result<usize, EndianError> {
+        T::write_le(value, self)
+    }
+
+    /// Writes any [`Endian`] value to the stream in big endian representation
+    #[inline]
+    fn write_be<T: Endian>(&mut self, value: &T) -> Result<usize, EndianError> {
+        T::write_be(value, self)
+    }
+
+    /// Writes any [`TryEndian`] value such as a `bool` or `char` to the stream in little endian
+    /// representation
+    #[inline]
+    fn try_write_le<T: TryEndian>(&mut self, value: &T) -> Result<usize, EndianError> {
+        T::write_le(value, self)
+    }
+
+    /// Writes any [`TryEndian`] value such as a `bool` or `char` to the stream in big endian
+    /// representation
+    #[inline]
+    fn try_write_be<T: TryEndian>(&mut self, value: &T) -> Result<usize, EndianError> {
+        T::write_be(value, self)
+    }
+
+    /// Writes an enum to the stream as its tag and the payload of its variant in `order`, see [`Tagged`]
+    #[inline]
+    fn write_tagged<T: Tagged>(&mut self, order: impl ByteOrder, value: &T) -> Result<usize, EndianError> {
+        value.write_tagged(self, order)
+    }
+
+    /// Writes `value` to the stream as a little endian unsigned integer of `nbytes` bytes
+    ///
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - [`EndianError::Overflow`] when `value` does not fit in `nbytes` bytes, or the stream error
+    fn write_uint_le(&mut self, value: u64, nbytes: usize) -> Result<usize, EndianError> {
+        let buf = uint::encode_uint(value, nbytes, false)?;
+        write_full::<u64, _>(self, &buf[..nbytes]).map(|_| nbytes)
+    }
+
+    /// Writes `value` to the stream as a big endian unsigned integer of `nbytes` bytes
+    ///
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - [`EndianError::Overflow`] when `value` does not fit in `nbytes` bytes, or the stream error
+    fn write_uint_be(&mut self, value: u64, nbytes: usize) -> Result<usize, EndianError> {
+        let buf = uint::encode_uint(value, nbytes, true)?;
+        write_full::<u64, _>(self, &buf[..nbytes]).map(|_| nbytes)
+    }
+
+    /// Writes `value` to the stream as a little endian two's complement integer of `nbytes` bytes
+    ///
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - [`EndianError::Overflow`] when `value` does not fit in `nbytes` bytes, or the stream error
+    fn write_int_le(&mut self, value: i64, nbytes: usize) -> Result<usize, EndianError> {
+        let buf = uint::encode_int(value, nbytes, false)?;
+        write_full::<i64, _>(self, &buf[..nbytes]).map(|_| nbytes)
+    }
+
+    /// Writes `value` to the stream as a big endian two's complement integer of `nbytes` bytes
+    ///
+    /// # Remarks
+    /// Panics unless `nbytes` is between 1 and 8
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - [`EndianError::Overflow`] when `value` does not fit in `nbytes` bytes, or the stream error
+    fn write_int_be(&mut self, value: i64, nbytes: usize) -> Result<usize, EndianError> {
+        let buf = uint::encode_int(value, nbytes, true)?;
+        write_full::<i64, _>(self, &buf[..nbytes]).map(|_| nbytes)
+    }
+
+    /// Writes `bytes` preceded by their length as an `L` in `order`
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written, including the prefix
+    /// Err - [`EndianError::Overflow`] when the length does not fit in an `L`, or the stream error
+    fn write_bytes_prefixed<L>(&mut self, order: impl ByteOrder, bytes: &[u8]) -> Result<usize, EndianError>
+        where L: Endian + TryFrom<usize>
+    {
+        let written = string::encode_len::<L>(bytes.len())?.write(self, order)?;
+        write_full::<[u8], _>(self, bytes).map_err(|e| e.offset_by(written))?;
+        Ok(written + bytes.len())
+    }
+
+    /// Writes the UTF-8 bytes of `value` preceded by their length as an `L` in `order`
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written, including the prefix
+    /// Err - [`EndianError::Overflow`] when the length does not fit in an `L`, or the stream error
+    fn write_str_prefixed<L>(&mut self, order: impl ByteOrder, value: &str) -> Result<usize, EndianError>
+        where L: Endian + TryFrom<usize>
+    {
+        self.write_bytes_prefixed::<L>(order, value.as_bytes())
+    }
+
+    /// Writes `value` including its NUL terminator
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written, including the terminator
+    /// Err - The stream error
+    fn write_cstr(&mut self, value: &CStr) -> Result<usize, EndianError> {
+        let bytes = value.to_bytes_with_nul();
+        write_full::<CStr, _>(self, bytes).map(|_| bytes.len())
+    }
+
+    /// Writes `bytes` as a fixed width field of `width` bytes, filling the rest with `pad`
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written, which is always `width`
+    /// Err - [`EndianError::TooLong`] when `bytes` is longer than `width`, or the stream error
+    fn write_bytes_padded(&mut self, bytes: &[u8], width: usize, pad: u8) -> Result<usize, EndianError> {
+        string::check_width(bytes.len(), width)?;
+
+        let mut field = bytes.to_vec();
+        field.resize(width, pad);
+        write_full::<[u8], _>(self, &field).map(|_| width)
+    }
+
+    /// Writes the UTF-8 bytes of `value` as a fixed width field of `width` bytes, filling the rest with
+    /// `pad`
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written, which is always `width`
+    /// Err - [`EndianError::TooLong`] when `value` is longer than `width` bytes, or the stream error
+    fn write_str_padded(&mut self, value: &str, width: usize, pad: u8) -> Result<usize, EndianError> {
+        self.write_bytes_padded(value.as_bytes(), width, pad)
+    }
+
+    /// Writes `value` as UTF-16 or UTF-32 text preceded by its length in code units as an `L`, both in
+    /// `order`
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written, including the prefix
+    /// Err - [`EndianError::Overflow`] when the length does not fit in an `L`, or the stream error
+    fn write_text_prefixed<L>(&mut self, encoding: TextEncoding, order: impl ByteOrder, value: &str) -> Result<usize, EndianError>
+        where L: Endian + TryFrom<usize>
+    {
+        let units = encoding.encoded_len(value);
+        let len = string::encode_len::<L>(units)?;
+
+        let mut bytes = vec![0; units * encoding.unit_size()];
+        text::encode_into(value, encoding, order.is_big(), &mut bytes);
+
+        let written = len.write(self, order)?;
+        write_full::<[u8], _>(self, &bytes).map_err(|e| e.offset_by(written))?;
+        Ok(written + bytes.len())
+    }
+
+    /// Writes `value` as UTF-16 or UTF-32 text in `order` followed by a zero code unit
+    ///
+    /// # Remarks
+    /// A NUL character in `value` ends the text early when it is read back
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written, including the terminator
+    /// Err - The stream error
+    fn write_text_nul(&mut self, encoding: TextEncoding, order: impl ByteOrder, value: &str) -> Result<usize, EndianError> {
+        let len = encoding.encoded_len(value) * encoding.unit_size();
+
+        let mut bytes = vec![0; len + encoding.unit_size()];
+        text::encode_into(value, encoding, order.is_big(), &mut bytes[..len]);
+        write_full::<[u8], _>(self, &bytes).map(|_| bytes.len())
+    }
+
+    /// Writes `value` as a fixed width field of `width` UTF-16 or UTF-32 code units in `order`, filling
+    /// the rest with zero code units
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - [`EndianError::TooLong`] when `value` needs more than `width` code units, or the stream error
+    fn write_text_padded(&mut self, encoding: TextEncoding, order: impl ByteOrder, value: &str, width: usize) -> Result<usize, EndianError> {
+        let len = text::byte_len(encoding, encoding.encoded_len(value), width)?;
+
+        let mut bytes = vec![0; width * encoding.unit_size()];
+        text::encode_into(value, encoding, order.is_big(), &mut bytes[..len]);
+        write_full::<[u8], _>(self, &bytes).map(|_| bytes.len())
+    }
+
+    /// Writes `value` to the stream as a variable length integer in encoding `E`
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - When `value` can not be represented by the encoding or the stream fails
+    fn write_varint<E: Varint>(&mut self, value: E::Value) -> Result<usize, EndianError> {
+        let (buf, len) = varint::encode::<E>(value)?;
+        write_full::<[u8], _>(self, &buf[..len]).map(|_| len)
+    }
+
+    /// Writes `value` as a `T` (e.g. `u32` or `u64`) in `order`
+    ///
+    /// # Remarks
+    /// See [`Endian`](crate::Endian) for why `usize` is stored as a fixed width integer
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - [`EndianError::Overflow`] when `value` does not fit in a `T`, or the stream error
+    fn write_usize_as<T>(&mut self, order: impl ByteOrder, value: usize) -> Result<usize, EndianError>
+        where T: Endian + TryFrom<usize>
+    {
+        uint::convert::<_, T>(value)?.write(self, order)
+    }
+
+    /// Writes `value` as a `T` (e.g. `i32` or `i64`) in `order`
+    ///
+    /// # Returns
+    /// See [`write_usize_as`](WriteEndianExt::write_usize_as)
+    fn write_isize_as<T>(&mut self, order: impl ByteOrder, value: isize) -> Result<usize, EndianError>
+        where T: Endian + TryFrom<isize>
+    {
+        uint::convert::<_, T>(value)?.write(self, order)
+    }
+}
+
+impl<W: Write> WriteEndianExt for W { }
+
+/// Reads the `nbytes` bytes of an integer of type `T` into the front of a buffer
+fn read_width<T, R: Read>(buf: &mut R, nbytes: usize) -> Result<[u8; 8], EndianError> {
+    uint::check_width(nbytes);
+
+    let mut out = [0; 8];
+    read_full::<T, _>(buf, &mut out[..nbytes])?;
+    Ok(out)
+}
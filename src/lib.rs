@@ -1,31 +1,485 @@
 //! # Tinybit
 //! A library inspired by `byteorder` focused on parsing primitive types from binary streams
 //! with robust error handling options
+//! 
+//! ## Features
+//! - `std` (default) - Reading and writing through `io::Read` and `io::Write`, implies `alloc`
+//! - `alloc` - Growable `Vec` backed writers
+//! - `derive` - Field wise `#[derive(Endian)]` and layout checked `#[derive(FromBytes)]` for structs,
+//!   tagged `#[derive(TryEndian)]` and `#[derive(Endian)]` for enums
+//! 
+//! Without `std` the crate only depends on `core`, values are read and written through
+//! [`SliceReader`] and [`SliceWriter`]
 
 #![deny(missing_docs)]
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::array;
+use core::mem;
+use core::num::Wrapping;
+use core::ptr;
+use core::slice;
+#[cfg(feature = "std")]
 use std::io::{self, Read, Write};
-use std::mem;
-use std::ptr;
-use std::slice;
-
-/// An error incurred when converting between binary representations
-pub enum EndianError {
-    /// Reached the end of the stream while reading/writing, contains the number of bytes written/read
-    EndOfStream(usize)
-}
 
-/// A trait providing various generic implementations for serializing primitive copy only types
-pub trait Endian: Copy + Default {
+mod bits;
+mod cast;
+// Compile fail checks, only built for doctests
+#[cfg(doctest)]
+mod compile_fail;
+mod cursor;
+mod error;
+#[cfg(feature = "std")]
+mod ext;
+mod order;
+mod storage;
+#[cfg(feature = "std")]
+mod stream;
+mod string;
+mod tagged;
+mod text;
+mod uint;
+mod valid;
+mod varint;
+
+pub use bits::{BitOrder, BitReader, BitSink, BitSource, BitWriter, LsbFirst, MsbFirst};
+pub use cast::{mut_from_bytes, ref_from_bytes, slice_from_bytes, slice_from_bytes_mut, FromBytes};
+#[cfg(feature = "alloc")]
+pub use cursor::VecWriter;
+pub use cursor::{SliceReader, SliceWriter};
+pub use error::EndianError;
+#[cfg(feature = "std")]
+pub use ext::{ReadEndianExt, WriteEndianExt};
+pub use order::{BigEndian, ByteOrder, Endianness, LittleEndian, NativeEndian, NetworkEndian};
+pub use storage::{F32Be, F64Be, I128Be, I16Be, I32Be, I64Be, U128Be, U16Be, U32Be, U64Be};
+pub use storage::{F32Le, F64Le, I128Le, I16Le, I32Le, I64Le, U128Le, U16Le, U32Le, U64Le};
+#[cfg(feature = "std")]
+pub use stream::{EndianReader, EndianWriter};
+pub use tagged::Tagged;
+pub use text::TextEncoding;
+pub use valid::TryEndian;
+pub use varint::{Leb128, QuicVarint, Sleb128, SqliteVarint, Varint, Vlq, ZigZag};
+
+/// A trait providing various generic implementations for serializing primitive types
+/// 
+/// # Remarks
+/// Implemented for all primitive integer and floating point types and `Option<NonZero*>`, types with
+/// invalid bit patterns such as `bool` and `char` implement [`TryEndian`] instead. Other types whose in
+/// memory representation is a single scalar value can opt into the raw implementation through
+/// [`Scalar`], structs can derive a field wise implementation with the `derive` feature
+/// 
+/// `usize` and `isize` are not implemented as their width depends on the target, the readers and
+/// writers store them as a fixed width integer through methods such as `write_usize_as::<u32>`, which
+/// keeps the encoding portable
+/// 
+/// The unchecked methods work on unaligned pointers, implementations must only access them a byte at a
+/// time (e.g. through `ptr::copy_nonoverlapping`) or through other unaligned safe methods. Safe code
+/// should use [`read_le_from`](Endian::read_le_from), [`write_le_into`](Endian::write_le_into) and
+/// their big endian equivalents instead
+pub trait Endian: Sized {
+    /// The number of bytes in the encoded representation of the type
+    const SIZE: usize;
+
+    /// Convert self into little endian representation and write the results to `buf`
+    /// 
+    /// # Remarks
+    /// Keeps writing until all `Self::SIZE` bytes are written, retrying interrupted writes. The default
+    /// implementation encodes into a temporary buffer with [`write_le_unchecked`](Endian::write_le_unchecked),
+    /// so implementations written without `std` keep compiling when it is enabled
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written to `buf`
+    /// Err - [`EndianError::Io`] with `WriteZero` when `buf` stops accepting bytes, or the underlying io error
+    #[cfg(feature = "std")]
+    fn write_le<W: Write>(&self, buf: &mut W) -> Result<usize, EndianError> {
+        let mut bytes = vec![0; Self::SIZE];
+        unsafe { self.write_le_unchecked(bytes.as_mut_ptr()); }
+        write_full::<Self, W>(buf, &bytes)?;
+        Ok(Self::SIZE)
+    }
+
     /// Convert self into little endian representation and write the results to `out`
     /// 
+    /// # Safety
+    /// Assumes that `out` is valid for writes of `Self::SIZE` bytes, `out` does not need to be aligned
+    /// 
+    /// # Returns
+    /// The number of bytes written to `out`
+    unsafe fn write_le_unchecked(&self, out: *mut u8) -> usize;
+
+    /// Convert self into big endian representation and write the results to `buf`
+    /// 
+    /// # Remarks
+    /// See [`write_le`](Endian::write_le)
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written to `buf`
+    /// Err - [`EndianError::Io`] with `WriteZero` when `buf` stops accepting bytes, or the underlying io error
+    #[cfg(feature = "std")]
+    fn write_be<W: Write>(&self, buf: &mut W) -> Result<usize, EndianError> {
+        let mut bytes = vec![0; Self::SIZE];
+        unsafe { self.write_be_unchecked(bytes.as_mut_ptr()); }
+        write_full::<Self, W>(buf, &bytes)?;
+        Ok(Self::SIZE)
+    }
+
+    /// Convert self into big endian representation and write the results to `out`
+    /// 
+    /// # Safety
+    /// Assumes that `out` is valid for writes of `Self::SIZE` bytes, `out` does not need to be aligned
+    /// 
+    /// # Returns
+    /// The number of bytes written to `out`
+    unsafe fn write_be_unchecked(&self, out: *mut u8) -> usize;
+
+    /// Creates self from the little endian bytes in `buf`
+    /// 
+    /// # Remarks
+    /// Keeps reading until all `Self::SIZE` bytes are read, retrying interrupted and short reads. The
+    /// default implementation reads into a temporary buffer and decodes it with
+    /// [`read_le_unchecked`](Endian::read_le_unchecked), so implementations written without `std` keep
+    /// compiling when it is enabled
+    /// 
+    /// # Returns
+    /// Ok with self
+    /// Err - [`EndianError::Io`] with `UnexpectedEof` when `buf` ends before enough bytes are read, or the
+    /// underlying io error
+    #[cfg(feature = "std")]
+    fn read_le<R: Read>(buf: &mut R) -> Result<Self, EndianError> {
+        let mut bytes = vec![0; Self::SIZE];
+        read_full::<Self, R>(buf, &mut bytes)?;
+        Ok(unsafe { Self::read_le_unchecked(bytes.as_ptr()) })
+    }
+
+    /// Creates self from the little endian bytes at `src`
+    /// 
+    /// # Safety
+    /// Assumes that `src` is valid for reads of `Self::SIZE` bytes, `src` does not need to be aligned
+    unsafe fn read_le_unchecked(src: *const u8) -> Self;
+
+    /// Creates self from the big endian bytes in `buf`
+    /// 
     /// # Remarks
-    /// Assumes that `out` is large enough to hold `mem::size_of::<Self>()` bytes, truncates otherwise
+    /// See [`read_le`](Endian::read_le)
+    /// 
+    /// # Returns
+    /// Ok with self
+    /// Err - [`EndianError::Io`] with `UnexpectedEof` when `buf` ends before enough bytes are read, or the
+    /// underlying io error
+    #[cfg(feature = "std")]
+    fn read_be<R: Read>(buf: &mut R) -> Result<Self, EndianError> {
+        let mut bytes = vec![0; Self::SIZE];
+        read_full::<Self, R>(buf, &mut bytes)?;
+        Ok(unsafe { Self::read_be_unchecked(bytes.as_ptr()) })
+    }
+
+    /// Creates self from the big endian bytes at `src`
+    /// 
+    /// # Safety
+    /// Assumes that `src` is valid for reads of `Self::SIZE` bytes, `src` does not need to be aligned
+    unsafe fn read_be_unchecked(src: *const u8) -> Self;
+
+    /// Convert self into little endian representation and write the results to the start of `out`
     /// 
     /// # Returns
     /// Ok - The number of bytes written to `out`
-    /// Err - The number of bytes written before reaching the end of `out`
-    fn write_le<W: Write>(&self, buf: &mut W) -> Result<usize, io::Error> {
+    /// Err - [`EndianError::EndOfStream`] when `out` is shorter than `Self::SIZE` bytes
+    fn write_le_into(&self, out: &mut [u8]) -> Result<usize, EndianError> {
+        if out.len() < Self::SIZE {
+            return Err(EndianError::EndOfStream(0));
+        }
+
+        Ok(unsafe { self.write_le_unchecked(out.as_mut_ptr()) })
+    }
+
+    /// Convert self into big endian representation and write the results to the start of `out`
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written to `out`
+    /// Err - [`EndianError::EndOfStream`] when `out` is shorter than `Self::SIZE` bytes
+    fn write_be_into(&self, out: &mut [u8]) -> Result<usize, EndianError> {
+        if out.len() < Self::SIZE {
+            return Err(EndianError::EndOfStream(0));
+        }
+
+        Ok(unsafe { self.write_be_unchecked(out.as_mut_ptr()) })
+    }
+
+    /// Creates self from the little endian bytes at the start of `src`
+    /// 
+    /// # Returns
+    /// Ok with self
+    /// Err - [`EndianError::EndOfStream`] when `src` is shorter than `Self::SIZE` bytes
+    fn read_le_from(src: &[u8]) -> Result<Self, EndianError> {
+        if src.len() < Self::SIZE {
+            return Err(EndianError::EndOfStream(0));
+        }
+
+        Ok(unsafe { Self::read_le_unchecked(src.as_ptr()) })
+    }
+
+    /// Creates self from the big endian bytes at the start of `src`
+    /// 
+    /// # Returns
+    /// Ok with self
+    /// Err - [`EndianError::EndOfStream`] when `src` is shorter than `Self::SIZE` bytes
+    fn read_be_from(src: &[u8]) -> Result<Self, EndianError> {
+        if src.len() < Self::SIZE {
+            return Err(EndianError::EndOfStream(0));
+        }
+
+        Ok(unsafe { Self::read_be_unchecked(src.as_ptr()) })
+    }
+
+    /// Convert self into the byte order given by `order` and write the results to `buf`
+    /// 
+    /// # Remarks
+    /// `order` is either a runtime [`Endianness`] or one of the zero sized markers such as [`BigEndian`],
+    /// which resolve to [`write_be`](Endian::write_be) or [`write_le`](Endian::write_le) at compile time
+    /// 
+    /// # Returns
+    /// See [`write_le`](Endian::write_le)
+    #[cfg(feature = "std")]
+    fn write<B: ByteOrder, W: Write>(&self, buf: &mut W, order: B) -> Result<usize, EndianError> {
+        if order.is_big() {
+            self.write_be(buf)
+        } else {
+            self.write_le(buf)
+        }
+    }
+
+    /// Creates self from the bytes in `buf` stored in the byte order given by `order`
+    /// 
+    /// # Remarks
+    /// `order` is either a runtime [`Endianness`] or one of the zero sized markers such as [`BigEndian`],
+    /// which resolve to [`read_be`](Endian::read_be) or [`read_le`](Endian::read_le) at compile time
+    /// 
+    /// # Returns
+    /// See [`read_le`](Endian::read_le)
+    #[cfg(feature = "std")]
+    fn read<B: ByteOrder, R: Read>(buf: &mut R, order: B) -> Result<Self, EndianError> {
+        if order.is_big() {
+            Self::read_be(buf)
+        } else {
+            Self::read_le(buf)
+        }
+    }
+
+    /// Convert self into the byte order given by `order` and write the results to the start of `out`
+    /// 
+    /// # Returns
+    /// See [`write_le_into`](Endian::write_le_into)
+    fn write_into<B: ByteOrder>(&self, out: &mut [u8], order: B) -> Result<usize, EndianError> {
+        if order.is_big() {
+            self.write_be_into(out)
+        } else {
+            self.write_le_into(out)
+        }
+    }
+
+    /// Creates self from the bytes at the start of `src` stored in the byte order given by `order`
+    /// 
+    /// # Returns
+    /// See [`read_le_from`](Endian::read_le_from)
+    fn read_from<B: ByteOrder>(src: &[u8], order: B) -> Result<Self, EndianError> {
+        if order.is_big() {
+            Self::read_be_from(src)
+        } else {
+            Self::read_le_from(src)
+        }
+    }
+
+    /// Convert self into its little endian representation as an array of `N` bytes
+    /// 
+    /// # Remarks
+    /// `N` must equal `Self::SIZE`, which is checked at compile time. It is usually inferred from the
+    /// context, e.g. `let bytes: [u8; 6] = header.to_le_bytes();`
+    fn to_le_bytes<const N: usize>(&self) -> [u8; N] {
+        const { assert!(N == Self::SIZE, "array length does not match Endian::SIZE") };
+
+        let mut bytes = [0; N];
+        unsafe { self.write_le_unchecked(bytes.as_mut_ptr()); }
+        bytes
+    }
+
+    /// Convert self into its big endian representation as an array of `N` bytes
+    /// 
+    /// # Remarks
+    /// `N` must equal `Self::SIZE`, which is checked at compile time. It is usually inferred from the
+    /// context, e.g. `let bytes: [u8; 6] = header.to_be_bytes();`
+    fn to_be_bytes<const N: usize>(&self) -> [u8; N] {
+        const { assert!(N == Self::SIZE, "array length does not match Endian::SIZE") };
+
+        let mut bytes = [0; N];
+        unsafe { self.write_be_unchecked(bytes.as_mut_ptr()); }
+        bytes
+    }
+
+    /// Creates self from its little endian representation
+    /// 
+    /// # Remarks
+    /// `N` must equal `Self::SIZE`, which is checked at compile time
+    fn from_le_bytes<const N: usize>(bytes: [u8; N]) -> Self {
+        const { assert!(N == Self::SIZE, "array length does not match Endian::SIZE") };
+
+        unsafe { Self::read_le_unchecked(bytes.as_ptr()) }
+    }
+
+    /// Creates self from its big endian representation
+    /// 
+    /// # Remarks
+    /// `N` must equal `Self::SIZE`, which is checked at compile time
+    fn from_be_bytes<const N: usize>(bytes: [u8; N]) -> Self {
+        const { assert!(N == Self::SIZE, "array length does not match Endian::SIZE") };
+
+        unsafe { Self::read_be_unchecked(bytes.as_ptr()) }
+    }
+
+    /// Convert each element of `items` into little endian representation and write the results to `buf`
+    /// 
+    /// # Remarks
+    /// The elements are encoded into a temporary buffer which is written in one go, so unbuffered streams
+    /// see a single write rather than one per element
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written to `buf`
+    /// Err - The error encountered while writing the buffer
+    #[cfg(feature = "std")]
+    fn write_slice_le<W: Write>(items: &[Self], buf: &mut W) -> Result<usize, EndianError> {
+        let mut bytes = vec![0; items.len() * Self::SIZE];
+        for (i, item) in items.iter().enumerate() {
+            unsafe { item.write_le_unchecked(bytes.as_mut_ptr().add(i * Self::SIZE)); }
+        }
+
+        write_full::<[Self], W>(buf, &bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Convert each element of `items` into big endian representation and write the results to `buf`
+    /// 
+    /// # Remarks
+    /// See [`write_slice_le`](Endian::write_slice_le)
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written to `buf`
+    /// Err - The error encountered while writing the buffer
+    #[cfg(feature = "std")]
+    fn write_slice_be<W: Write>(items: &[Self], buf: &mut W) -> Result<usize, EndianError> {
+        let mut bytes = vec![0; items.len() * Self::SIZE];
+        for (i, item) in items.iter().enumerate() {
+            unsafe { item.write_be_unchecked(bytes.as_mut_ptr().add(i * Self::SIZE)); }
+        }
+
+        write_full::<[Self], W>(buf, &bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Fills `items` with elements created from the little endian bytes in `buf`
+    /// 
+    /// # Remarks
+    /// The bytes of every element are read into a temporary buffer in one go, so unbuffered streams see
+    /// as few reads as possible rather than one per element
+    /// 
+    /// # Returns
+    /// Ok when every element of `items` was read
+    /// Err when `buf` does not contain enough bytes, elements read in full before the failure are kept
+    #[cfg(feature = "std")]
+    fn read_into_slice_le<R: Read>(items: &mut [Self], buf: &mut R) -> Result<(), EndianError> {
+        let mut bytes = vec![0; items.len() * Self::SIZE];
+        let result = read_full::<[Self], R>(buf, &mut bytes);
+        let count = complete::<Self>(&result, items.len());
+        for (i, item) in items[..count].iter_mut().enumerate() {
+            *item = unsafe { Self::read_le_unchecked(bytes.as_ptr().add(i * Self::SIZE)) };
+        }
+
+        result
+    }
+
+    /// Fills `items` with elements created from the big endian bytes in `buf`
+    /// 
+    /// # Remarks
+    /// See [`read_into_slice_le`](Endian::read_into_slice_le)
+    /// 
+    /// # Returns
+    /// Ok when every element of `items` was read
+    /// Err when `buf` does not contain enough bytes, elements read in full before the failure are kept
+    #[cfg(feature = "std")]
+    fn read_into_slice_be<R: Read>(items: &mut [Self], buf: &mut R) -> Result<(), EndianError> {
+        let mut bytes = vec![0; items.len() * Self::SIZE];
+        let result = read_full::<[Self], R>(buf, &mut bytes);
+        let count = complete::<Self>(&result, items.len());
+        for (i, item) in items[..count].iter_mut().enumerate() {
+            *item = unsafe { Self::read_be_unchecked(bytes.as_ptr().add(i * Self::SIZE)) };
+        }
+
+        result
+    }
+}
+
+/// Derives [`Endian`] for a struct, converting each field in declaration order, or for an enum with an
+/// `#[endian(unknown)]` catch-all variant, which also implements [`Tagged`]
+/// 
+/// Available with the `derive` feature
+#[cfg(feature = "derive")]
+pub use tinybit_derive::Endian;
+
+/// Derives [`TryEndian`] for an enum with an integer `#[repr]`, encoding the tag followed by the fields
+/// of the variant padded to the largest variant, along with the unpadded [`Tagged`] encoding
+/// 
+/// Available with the `derive` feature
+#[cfg(feature = "derive")]
+pub use tinybit_derive::TryEndian;
+
+/// Derives [`FromBytes`] for a `#[repr(C)]` struct, verifying it has no padding at compile time
+/// 
+/// Available with the `derive` feature
+#[cfg(feature = "derive")]
+pub use tinybit_derive::FromBytes;
+
+#[doc(hidden)]
+pub mod __private {
+    use crate::EndianError;
+
+    /// Creates the error for an enum tag which does not match any variant
+    pub fn invalid_value(type_name: &'static str, bytes: &[u8]) -> EndianError {
+        EndianError::invalid_value(type_name, bytes)
+    }
+}
+
+/// Marker for types represented in memory by a single scalar value
+/// 
+/// Types implementing this trait get an [`Endian`] implementation which copies their raw bytes
+/// and reverses them as a whole when the target byte order differs from the native one
+/// 
+/// # Safety
+/// Implementors must guarantee the following
+/// - Every bit pattern of `mem::size_of::<Self>()` bytes is a valid value of the type
+/// - The type contains no padding bytes
+/// - The type is a single value whose byte order is reversed as a unit (e.g. a `#[repr(transparent)]`
+///   wrapper around a primitive, not a struct with several fields)
+pub unsafe trait Scalar: Copy + Default { }
+
+macro_rules! impl_scalar {
+    ($($ty:ty),*) => {
+        $(unsafe impl Scalar for $ty { })*
+    };
+}
+
+impl_scalar!(u8, u16, u32, u64, u128);
+impl_scalar!(i8, i16, i32, i64, i128);
+impl_scalar!(f32, f64);
+
+unsafe impl<T: Scalar> Scalar for Wrapping<T> { }
+
+impl<T: Scalar> Endian for T {
+    const SIZE: usize = mem::size_of::<Self>();
+
+    #[cfg(feature = "std")]
+    fn write_le<W: Write>(&self, buf: &mut W) -> Result<usize, EndianError> {
         // Type is a ZST, can't copy anything but it still "succeeds"
         // (why anyone would ever try this I have no idea)
         let size = mem::size_of::<Self>();
@@ -37,24 +491,18 @@ pub trait Endian: Copy + Default {
             // Construct the data
             let mut tmp = *self;
             let src = &mut tmp as *mut _ as *mut u8;
-            let slice = slice::from_raw_parts(src, size);
 
-            // Ensure correct encoding
+            // Ensure correct encoding, before any reference to the bytes exists
             transform_le(src, size);
 
             // Write to the buffer
-            Ok(buf.write(slice)?)
+            let slice = slice::from_raw_parts(src, size);
+            write_full::<Self, W>(buf, slice)?;
         }
+
+        Ok(size)
     }
 
-    /// Convert self into little endian representation and write the results to `out`
-    /// 
-    /// # Safety
-    /// Assumes that `out` is valid, well aligned, and large enough to hold `mem::size_of::<Self>()` bytes
-    /// 
-    /// # Returns
-    /// Ok - The number of bytes written to `out`
-    /// Err - The number of bytes written before reaching the end of `out`
     unsafe fn write_le_unchecked(&self, out: *mut u8) -> usize {
         // Type is a ZST, can't copy anything but it still "succeeds"
         // (why anyone would ever try this I have no idea)
@@ -70,15 +518,8 @@ pub trait Endian: Copy + Default {
         size
     }
 
-    /// Convert self into big endian representation and write the results to `out`
-    /// 
-    /// # Remarks
-    /// Assumes that `out` is large enough to hold `mem::size_of::<Self>()` bytes, truncates otherwise
-    /// 
-    /// # Returns
-    /// Ok - The number of bytes written to `out`
-    /// Err - The number of bytes written before reaching the end of `out`
-    fn write_be<W: Write>(&self, buf: &mut W) -> Result<usize, io::Error> {
+    #[cfg(feature = "std")]
+    fn write_be<W: Write>(&self, buf: &mut W) -> Result<usize, EndianError> {
         // Type is a ZST, can't copy anything but it still "succeeds"
         // (why anyone would ever try this I have no idea)
         let size = mem::size_of::<Self>();
@@ -90,24 +531,18 @@ pub trait Endian: Copy + Default {
             // Construct the data
             let mut tmp = *self;
             let src = &mut tmp as *mut _ as *mut u8;
-            let slice = slice::from_raw_parts(src, size);
 
-            // Ensure correct encoding
+            // Ensure correct encoding, before any reference to the bytes exists
             transform_be(src, size);
 
             // Write to the buffer
-            Ok(buf.write(slice)?)
+            let slice = slice::from_raw_parts(src, size);
+            write_full::<Self, W>(buf, slice)?;
         }
+
+        Ok(size)
     }
 
-    /// Convert self into big endian representation and write the results to `out`
-    /// 
-    /// # Safety
-    /// Assumes that `out` is valid, well aligned, and large enough to hold `mem::size_of::<Self>()` bytes
-    /// 
-    /// # Returns
-    /// Ok - The number of bytes written to `out`
-    /// Err - The number of bytes written before reaching the end of `out`
     unsafe fn write_be_unchecked(&self, out: *mut u8) -> usize {
         // Type is a ZST, can't copy anything but it still "succeeds"
         // (why anyone would ever try this I have no idea)
@@ -123,25 +558,19 @@ pub trait Endian: Copy + Default {
         size
     }
 
-    /// Creates self from the little endian bytes in `buf`
-    /// 
-    /// # Returns
-    /// Ok with self
-    /// Err when `buf` does not contain enough bytes
-    fn read_le<R: Read>(buf: &mut R) -> Result<Self, io::Error> {
+    #[cfg(feature = "std")]
+    fn read_le<R: Read>(buf: &mut R) -> Result<Self, EndianError> {
         let size = mem::size_of::<Self>();
         if size == 0 {
             return Ok(Default::default());
         }
 
         unsafe {
-            let mut result = mem::MaybeUninit::zeroed();
+            let mut result = mem::MaybeUninit::<Self>::zeroed();
             let dst = result.as_mut_ptr() as *mut u8;
             let slice = slice::from_raw_parts_mut(dst, size);
 
-            if buf.read(slice)? < size {
-                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
-            }
+            read_full::<Self, R>(buf, slice)?;
 
             transform_le(dst, size);
 
@@ -149,19 +578,13 @@ pub trait Endian: Copy + Default {
         }
     }
 
-    /// Creates self from the little endian bytes at `src`
-    /// 
-    /// # Safety
-    /// Assumes the following
-    /// - `src` is valid and well aligned
-    /// - `src` is valid for `mem::size_of::<Self>()` bytes
     unsafe fn read_le_unchecked(src: *const u8) -> Self {
         let size = mem::size_of::<Self>();
         if size == 0 {
-            return mem::zeroed();
+            return Default::default();
         }
 
-        let mut result = mem::MaybeUninit::zeroed();
+        let mut result = mem::MaybeUninit::<Self>::zeroed();
         let dst = result.as_mut_ptr() as *mut u8;
 
         ptr::copy_nonoverlapping(src, dst, size);
@@ -171,25 +594,19 @@ pub trait Endian: Copy + Default {
         result.assume_init()
     }
 
-    /// Creates self from the big endian bytes in `buf`
-    /// 
-    /// # Returns
-    /// Ok with self
-    /// Err when `buf` does not contain enough bytes
-    fn read_be<R: Read>(buf: &mut R) -> Result<Self, io::Error> {
+    #[cfg(feature = "std")]
+    fn read_be<R: Read>(buf: &mut R) -> Result<Self, EndianError> {
         let size = mem::size_of::<Self>();
         if size == 0 {
-            return Ok(unsafe { mem::zeroed() });// ZST optimizes out to a noop
+            return Ok(Default::default());
         }
 
         unsafe {
-            let mut result = mem::MaybeUninit::zeroed();
+            let mut result = mem::MaybeUninit::<Self>::zeroed();
             let dst = result.as_mut_ptr() as *mut u8;
             let slice = slice::from_raw_parts_mut(dst, size);
 
-            if buf.read(slice)? < size {
-                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
-            }
+            read_full::<Self, R>(buf, slice)?;
 
             transform_be(dst, size);
 
@@ -197,19 +614,13 @@ pub trait Endian: Copy + Default {
         }
     }
 
-    /// Creates self from the big endian bytes in `buf`
-    /// 
-    /// # Safety
-    /// Assumes the following
-    /// - `src` is valid and well aligned
-    /// - `src` is valid for `mem::size_of::<Self>()` bytes
     unsafe fn read_be_unchecked(src: *const u8) -> Self {
         let size = mem::size_of::<Self>();
         if size == 0 {
-            return mem::zeroed();// ZST optimizes out to a noop
+            return Default::default();
         }
 
-        let mut result = mem::MaybeUninit::zeroed();
+        let mut result = mem::MaybeUninit::<Self>::zeroed();
         let dst = result.as_mut_ptr() as *mut u8;
 
         ptr::copy_nonoverlapping(src, dst, size);
@@ -220,53 +631,234 @@ pub trait Endian: Copy + Default {
     }
 }
 
-// Blanket impl to cover all trivial types
-impl<T> Endian for T
-    where T: Copy + Default
-{ }
+/// Reads from `buf` until `dst` is full, `T` is the type being read
+/// 
+/// Unlike a single `read` call this never mistakes a short read for the end of the stream, reads are
+/// retried until `buf` reports the end of the stream by returning `0`
+#[cfg(feature = "std")]
+pub(crate) fn read_full<T: ?Sized, R: Read>(buf: &mut R, dst: &mut [u8]) -> Result<(), EndianError> {
+    let mut read = 0;
+    while read < dst.len() {
+        match buf.read(&mut dst[read..]) {
+            Ok(0) => return Err(EndianError::io::<T>(dst.len(), read, io::ErrorKind::UnexpectedEof.into())),
+            Ok(n) => read += n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(EndianError::io::<T>(dst.len(), read, e))
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes all of `src` to `buf`, `T` is the type being written
+/// 
+/// Partial writes are continued until every byte is written, a write accepting no bytes is reported as
+/// `WriteZero`
+#[cfg(feature = "std")]
+pub(crate) fn write_full<T: ?Sized, W: Write>(buf: &mut W, src: &[u8]) -> Result<(), EndianError> {
+    let mut written = 0;
+    while written < src.len() {
+        match buf.write(&src[written..]) {
+            Ok(0) => return Err(EndianError::io::<T>(src.len(), written, io::ErrorKind::WriteZero.into())),
+            Ok(n) => written += n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(EndianError::io::<T>(src.len(), written, e))
+        }
+    }
+
+    Ok(())
+}
+
+/// The number of the `len` elements of type `T` which were read in full by a bulk read with `result`
+#[cfg(feature = "std")]
+fn complete<T: Endian>(result: &Result<(), EndianError>, len: usize) -> usize {
+    match result {
+        _ if T::SIZE == 0 => len,
+        Ok(()) => len,
+        Err(EndianError::Io { actual, .. }) => actual / T::SIZE,
+        Err(_) => 0
+    }
+}
+
+// Arrays are converted element by element so the element order is preserved, streams go through the
+// default implementations which move the whole array in one read/write
+impl<T: Endian, const N: usize> Endian for [T; N] {
+    const SIZE: usize = T::SIZE * N;
+
+    unsafe fn write_le_unchecked(&self, out: *mut u8) -> usize {
+        for (i, item) in self.iter().enumerate() {
+            item.write_le_unchecked(out.add(i * T::SIZE));
+        }
+
+        Self::SIZE
+    }
+
+    unsafe fn write_be_unchecked(&self, out: *mut u8) -> usize {
+        for (i, item) in self.iter().enumerate() {
+            item.write_be_unchecked(out.add(i * T::SIZE));
+        }
+
+        Self::SIZE
+    }
+
+    unsafe fn read_le_unchecked(src: *const u8) -> Self {
+        array::from_fn(|i| unsafe { T::read_le_unchecked(src.add(i * T::SIZE)) })
+    }
+
+    unsafe fn read_be_unchecked(src: *const u8) -> Self {
+        array::from_fn(|i| unsafe { T::read_be_unchecked(src.add(i * T::SIZE)) })
+    }
+}
+
+// Tuples are converted element by element in declaration order, streams go through the default
+// implementations which move the whole tuple in one read/write
+macro_rules! impl_tuple {
+    ($($name:ident . $idx:tt),+) => {
+        impl<$($name: Endian),+> Endian for ($($name,)+) {
+            const SIZE: usize = 0 $(+ $name::SIZE)+;
+
+            #[allow(unused_assignments)]
+            unsafe fn write_le_unchecked(&self, out: *mut u8) -> usize {
+                let mut offset = 0;
+                $(offset += self.$idx.write_le_unchecked(out.add(offset));)+
+                offset
+            }
+
+            #[allow(unused_assignments)]
+            unsafe fn write_be_unchecked(&self, out: *mut u8) -> usize {
+                let mut offset = 0;
+                $(offset += self.$idx.write_be_unchecked(out.add(offset));)+
+                offset
+            }
+
+            #[allow(unused_assignments)]
+            unsafe fn read_le_unchecked(src: *const u8) -> Self {
+                let mut offset = 0;
+                ($({
+                    let item = $name::read_le_unchecked(src.add(offset));
+                    offset += $name::SIZE;
+                    item
+                },)+)
+            }
+
+            #[allow(unused_assignments)]
+            unsafe fn read_be_unchecked(src: *const u8) -> Self {
+                let mut offset = 0;
+                ($({
+                    let item = $name::read_be_unchecked(src.add(offset));
+                    offset += $name::SIZE;
+                    item
+                },)+)
+            }
+        }
+    };
+}
+
+impl_tuple!(A.0);
+impl_tuple!(A.0, B.1);
+impl_tuple!(A.0, B.1, C.2);
+impl_tuple!(A.0, B.1, C.2, D.3);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9, K.10);
+impl_tuple!(A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9, K.10, L.11);
+
+/// Converts every element of `items` between native and little endian representation in place
+/// 
+/// # Remarks
+/// The conversion is its own inverse, use it after filling `items` with raw little endian bytes or
+/// before handing them out as such. Is a noop on little endian targets
+pub fn convert_slice_le<T: Scalar>(items: &mut [T]) {
+    if cfg!(target_endian = "big") {
+        swap_slice(items);
+    }
+}
+
+/// Converts every element of `items` between native and big endian representation in place
+/// 
+/// # Remarks
+/// The conversion is its own inverse, use it after filling `items` with raw big endian bytes (e.g.
+/// through [`FromBytes`]) or before handing them out as such. Is a noop on big endian targets
+pub fn convert_slice_be<T: Scalar>(items: &mut [T]) {
+    if cfg!(target_endian = "little") {
+        swap_slice(items);
+    }
+}
+
+/// Converts every element of `items` between native representation and the byte order given by `order`
+/// in place
+/// 
+/// # Remarks
+/// See [`convert_slice_le`] and [`convert_slice_be`]
+pub fn convert_slice<T: Scalar, B: ByteOrder>(items: &mut [T], order: B) {
+    if order.is_big() {
+        convert_slice_be(items)
+    } else {
+        convert_slice_le(items)
+    }
+}
+
+/// Reverses the bytes of every element of `items`
+fn swap_slice<T: Scalar>(items: &mut [T]) {
+    // A plain loop over fixed size swaps is vectorized into wide byte shuffles by the compiler
+    for item in items {
+        // Scalars have no padding and accept every bit pattern, so any byte order is a valid value
+        unsafe { swap_bytes(item as *mut T as *mut u8, mem::size_of::<T>()); }
+    }
+}
+
+/// Reverses the `len` bytes at `ptr`
+/// 
+/// # Remarks
+/// `len` is the size of a scalar and known at compile time, so this folds into a single `swap_bytes`
+/// of the matching integer for primitive sizes
+/// 
+/// # Safety
+/// Assumes that `ptr` is valid for reads and writes of `len` bytes, `ptr` does not need to be aligned
+#[inline(always)]
+unsafe fn swap_bytes(ptr: *mut u8, len: usize) {
+    match len {
+        0 | 1 => { },
+        2 => ptr::write_unaligned(ptr as *mut u16, ptr::read_unaligned(ptr as *const u16).swap_bytes()),
+        4 => ptr::write_unaligned(ptr as *mut u32, ptr::read_unaligned(ptr as *const u32).swap_bytes()),
+        8 => ptr::write_unaligned(ptr as *mut u64, ptr::read_unaligned(ptr as *const u64).swap_bytes()),
+        16 => ptr::write_unaligned(ptr as *mut u128, ptr::read_unaligned(ptr as *const u128).swap_bytes()),
+        _ => slice::from_raw_parts_mut(ptr, len).reverse()
+    }
+}
 
 /// Transform a binary representation into little endian format
 /// 
 /// Is a noop when the target endianess matches the native endianess
 /// 
 /// # Safety
-/// Assumes the following
-/// - `ptr` is valid and well aligned
-/// - `ptr` is valid for `len` bytes
+/// Assumes that `ptr` is valid for reads and writes of `len` bytes, `ptr` does not need to be aligned
 #[allow(unused_variables)]
+#[inline(always)]
 unsafe fn transform_le(ptr: *mut u8, len: usize) {
     // Little endian systems are a noop so do nothing
 
     // Big endian systems need to flip the bytes in place to get the little endian representation
     #[cfg(target_endian = "big")] {
-        let half_len = len / 2;
-        if half_len > 0 {
-            for i in 0..half_len {
-                ptr::swap(ptr.add(i), ptr.add(len - i - 1));
-            }
-        }
+        swap_bytes(ptr, len);
     }
 }
 
 /// Transform a binary representation into big endian format
 /// 
 /// # Safety
-/// Assumes the following
-/// - `ptr` is valid and well aligned
-/// - `ptr` is valid for `len` bytes
+/// Assumes that `ptr` is valid for reads and writes of `len` bytes, `ptr` does not need to be aligned
+#[allow(unused_variables)]
+#[inline(always)]
 unsafe fn transform_be(ptr: *mut u8, len: usize) {
     // Big endian systems can just return here as it's already correct
-    #[cfg(target_endian = "big")] { 
-        return;
-    }
 
     // Little endian systems need to flip the bytes in place to get the big endian representation
     #[cfg(target_endian = "little")] {
-        let half_len = len / 2;
-        if half_len > 0 {
-            for i in 0..half_len {
-                ptr::swap(ptr.add(i), ptr.add(len - i - 1));
-            }
-        }
+        swap_bytes(ptr, len);
     }
-}
\ No newline at end of file
+}
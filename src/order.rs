@@ -0,0 +1,118 @@
+//! Byte orders known at compile time or only at runtime
+
+/// A byte order selected at runtime
+///
+/// # Remarks
+/// Useful for formats which declare their byte order in a header (e.g. TIFF, pcap or ELF), values can
+/// then be converted with [`Endian::read_from`](crate::Endian::read_from) and friends instead of branching
+/// between the little and big endian methods at every call site
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// Least significant byte first
+    Little,
+    /// Most significant byte first
+    Big,
+    /// The byte order of the target platform
+    Native
+}
+
+impl Endianness {
+    /// Resolves [`Native`](Endianness::Native) into the byte order of the target platform
+    pub fn resolve(self) -> Self {
+        match self {
+            Endianness::Native if cfg!(target_endian = "big") => Endianness::Big,
+            Endianness::Native => Endianness::Little,
+            order => order
+        }
+    }
+
+    /// Returns true when the byte order resolves to little endian
+    pub fn is_little(self) -> bool {
+        self.resolve() == Endianness::Little
+    }
+
+    /// Returns true when the byte order resolves to big endian
+    pub fn is_big(self) -> bool {
+        self.resolve() == Endianness::Big
+    }
+}
+
+impl Default for Endianness {
+    /// Defaults to the byte order of the target platform
+    fn default() -> Self {
+        Endianness::Native
+    }
+}
+
+/// A byte order, either fixed at compile time or selected at runtime
+///
+/// # Remarks
+/// Implemented by the zero sized [`LittleEndian`] and [`BigEndian`] markers, which make byte order
+/// generic code resolve to the matching little or big endian conversion at compile time, and by
+/// [`Endianness`] for byte orders only known at runtime
+/// 
+/// This trait is sealed and can not be implemented outside of this crate
+pub trait ByteOrder: Copy + Default + private::Sealed {
+    /// The byte order as a runtime value
+    fn endianness(self) -> Endianness;
+
+    /// Returns true when the byte order resolves to little endian
+    #[inline]
+    fn is_little(self) -> bool {
+        self.endianness().is_little()
+    }
+
+    /// Returns true when the byte order resolves to big endian
+    #[inline]
+    fn is_big(self) -> bool {
+        self.endianness().is_big()
+    }
+}
+
+/// Little endian byte order fixed at compile time
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LittleEndian;
+
+/// Big endian byte order fixed at compile time
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigEndian;
+
+/// Network byte order, which is big endian
+pub type NetworkEndian = BigEndian;
+
+/// The byte order of the target platform fixed at compile time
+#[cfg(target_endian = "little")]
+pub type NativeEndian = LittleEndian;
+
+/// The byte order of the target platform fixed at compile time
+#[cfg(target_endian = "big")]
+pub type NativeEndian = BigEndian;
+
+impl ByteOrder for LittleEndian {
+    #[inline]
+    fn endianness(self) -> Endianness {
+        Endianness::Little
+    }
+}
+
+impl ByteOrder for BigEndian {
+    #[inline]
+    fn endianness(self) -> Endianness {
+        Endianness::Big
+    }
+}
+
+impl ByteOrder for Endianness {
+    #[inline]
+    fn endianness(self) -> Endianness {
+        self
+    }
+}
+
+mod private {
+    pub trait Sealed { }
+
+    impl Sealed for super::LittleEndian { }
+    impl Sealed for super::BigEndian { }
+    impl Sealed for super::Endianness { }
+}
@@ -0,0 +1,174 @@
+//! Integer and float types stored in a fixed byte order
+//!
+//! Each type wraps the encoded bytes of its value, so its in memory representation is the same on
+//! every platform and it has an alignment of 1. This makes them suitable as fields of `#[repr(C)]`
+//! structs overlaying on disk or wire data
+
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::{Read, Write};
+
+use crate::Endian;
+#[cfg(feature = "std")]
+use crate::{read_full, write_full, EndianError};
+
+macro_rules! impl_storage {
+    ($($name:ident($ty:ty, $size:literal, $to:ident, $from:ident, $order:literal);)*) => {
+        $(
+            #[doc = concat!("A `", stringify!($ty), "` stored as ", $order, " endian bytes")]
+            ///
+            /// # Remarks
+            /// Converting through [`Endian`] copies the stored bytes as is regardless of the method
+            /// called, the byte order is a property of the type
+            #[derive(Clone, Copy, Default)]
+            #[repr(transparent)]
+            pub struct $name([u8; $size]);
+
+            impl $name {
+                #[doc = concat!("Stores `value` as ", $order, " endian bytes")]
+                #[inline]
+                pub fn new(value: $ty) -> Self {
+                    $name(value.$to())
+                }
+
+                /// Wraps bytes which are already in the byte order of the type
+                #[inline]
+                pub const fn from_bytes(bytes: [u8; $size]) -> Self {
+                    $name(bytes)
+                }
+
+                /// The stored bytes
+                #[inline]
+                pub const fn to_bytes(self) -> [u8; $size] {
+                    self.0
+                }
+
+                /// Decodes the stored value
+                #[inline]
+                pub fn get(self) -> $ty {
+                    <$ty>::$from(self.0)
+                }
+
+                /// Replaces the stored value with `value`
+                #[inline]
+                pub fn set(&mut self, value: $ty) {
+                    self.0 = value.$to();
+                }
+            }
+
+            impl From<$ty> for $name {
+                #[inline]
+                fn from(value: $ty) -> Self {
+                    $name::new(value)
+                }
+            }
+
+            impl From<$name> for $ty {
+                #[inline]
+                fn from(value: $name) -> Self {
+                    value.get()
+                }
+            }
+
+            impl fmt::Debug for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.debug_tuple(stringify!($name)).field(&self.get()).finish()
+                }
+            }
+
+            impl Endian for $name {
+                const SIZE: usize = $size;
+
+                #[cfg(feature = "std")]
+                fn write_le<W: Write>(&self, buf: &mut W) -> Result<usize, EndianError> {
+                    write_full::<Self, W>(buf, &self.0)?;
+                    Ok($size)
+                }
+
+                unsafe fn write_le_unchecked(&self, out: *mut u8) -> usize {
+                    self.0.write_le_unchecked(out)
+                }
+
+                #[cfg(feature = "std")]
+                fn write_be<W: Write>(&self, buf: &mut W) -> Result<usize, EndianError> {
+                    write_full::<Self, W>(buf, &self.0)?;
+                    Ok($size)
+                }
+
+                unsafe fn write_be_unchecked(&self, out: *mut u8) -> usize {
+                    self.0.write_be_unchecked(out)
+                }
+
+                #[cfg(feature = "std")]
+                fn read_le<R: Read>(buf: &mut R) -> Result<Self, EndianError> {
+                    let mut bytes = [0; $size];
+                    read_full::<Self, R>(buf, &mut bytes)?;
+                    Ok($name(bytes))
+                }
+
+                unsafe fn read_le_unchecked(src: *const u8) -> Self {
+                    $name(<[u8; $size]>::read_le_unchecked(src))
+                }
+
+                #[cfg(feature = "std")]
+                fn read_be<R: Read>(buf: &mut R) -> Result<Self, EndianError> {
+                    let mut bytes = [0; $size];
+                    read_full::<Self, R>(buf, &mut bytes)?;
+                    Ok($name(bytes))
+                }
+
+                unsafe fn read_be_unchecked(src: *const u8) -> Self {
+                    $name(<[u8; $size]>::read_be_unchecked(src))
+                }
+            }
+        )*
+    };
+}
+
+// Integers compare by value, which for a fixed byte order is the same as comparing the bytes
+macro_rules! impl_storage_eq {
+    ($($name:ident),*) => {
+        $(
+            impl PartialEq for $name {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    self.0 == other.0
+                }
+            }
+
+            impl Eq for $name { }
+
+            impl core::hash::Hash for $name {
+                fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                    self.0.hash(state);
+                }
+            }
+        )*
+    };
+}
+
+impl_storage! {
+    U16Le(u16, 2, to_le_bytes, from_le_bytes, "little");
+    U32Le(u32, 4, to_le_bytes, from_le_bytes, "little");
+    U64Le(u64, 8, to_le_bytes, from_le_bytes, "little");
+    U128Le(u128, 16, to_le_bytes, from_le_bytes, "little");
+    I16Le(i16, 2, to_le_bytes, from_le_bytes, "little");
+    I32Le(i32, 4, to_le_bytes, from_le_bytes, "little");
+    I64Le(i64, 8, to_le_bytes, from_le_bytes, "little");
+    I128Le(i128, 16, to_le_bytes, from_le_bytes, "little");
+    F32Le(f32, 4, to_le_bytes, from_le_bytes, "little");
+    F64Le(f64, 8, to_le_bytes, from_le_bytes, "little");
+    U16Be(u16, 2, to_be_bytes, from_be_bytes, "big");
+    U32Be(u32, 4, to_be_bytes, from_be_bytes, "big");
+    U64Be(u64, 8, to_be_bytes, from_be_bytes, "big");
+    U128Be(u128, 16, to_be_bytes, from_be_bytes, "big");
+    I16Be(i16, 2, to_be_bytes, from_be_bytes, "big");
+    I32Be(i32, 4, to_be_bytes, from_be_bytes, "big");
+    I64Be(i64, 8, to_be_bytes, from_be_bytes, "big");
+    I128Be(i128, 16, to_be_bytes, from_be_bytes, "big");
+    F32Be(f32, 4, to_be_bytes, from_be_bytes, "big");
+    F64Be(f64, 8, to_be_bytes, from_be_bytes, "big");
+}
+
+impl_storage_eq!(U16Le, U32Le, U64Le, U128Le, I16Le, I32Le, I64Le, I128Le);
+impl_storage_eq!(U16Be, U32Be, U64Be, U128Be, I16Be, I32Be, I64Be, I128Be);
@@ -0,0 +1,319 @@
+//! Stream wrappers converting every value with a configured byte order
+
+use std::convert::{TryFrom, TryInto};
+use std::io::{self, Read, Write};
+
+use crate::{ByteOrder, Endian, EndianError, Endianness, ReadEndianExt, Tagged, TryEndian, WriteEndianExt};
+
+/// Wraps an `io::Read`, reading every value in a configured byte order
+///
+/// # Remarks
+/// The byte order is either a runtime [`Endianness`] or one of the [`ByteOrder`] markers fixing it at
+/// compile time. Fields with a fixed byte order can still be read through
+/// [`get_mut`](EndianReader::get_mut)
+///
+/// Counts the bytes read through it, errors report offsets from the position the wrapper was created
+/// at rather than from the start of the failing value
+#[derive(Debug)]
+pub struct EndianReader<R, B = Endianness> {
+    inner: R,
+    order: B,
+    pos: usize
+}
+
+impl<R: Read, B: ByteOrder> EndianReader<R, B> {
+    /// Creates a reader converting values from `order`
+    pub fn new(inner: R, order: B) -> Self {
+        EndianReader {
+            inner,
+            order,
+            pos: 0
+        }
+    }
+
+    /// Reads a value in the configured byte order
+    pub fn read<T: Endian>(&mut self) -> Result<T, EndianError> {
+        self.track(|inner, order| T::read(inner, order))
+    }
+
+    /// Reads a validated value such as a `bool` or `char` in the configured byte order
+    pub fn try_read<T: TryEndian>(&mut self) -> Result<T, EndianError> {
+        self.track(|inner, order| T::try_read(inner, order))
+    }
+
+    /// Reads an enum stored as its tag and the payload of its variant in the configured byte order, see
+    /// [`Tagged`]
+    pub fn read_tagged<T: Tagged>(&mut self) -> Result<T, EndianError> {
+        self.track(|inner, order| T::read_tagged(inner, order))
+    }
+
+    /// Reads a `usize` stored as a `T` (e.g. `u32` or `u64`) in the configured byte order
+    /// 
+    /// # Returns
+    /// See [`ReadEndianExt::read_usize_as`]
+    pub fn read_usize_as<T>(&mut self) -> Result<usize, EndianError>
+        where T: Endian + TryInto<usize>
+    {
+        self.track(|inner, order| inner.read_usize_as::<T>(order))
+    }
+
+    /// Reads an `isize` stored as a `T` (e.g. `i32` or `i64`) in the configured byte order
+    /// 
+    /// # Returns
+    /// See [`ReadEndianExt::read_usize_as`]
+    pub fn read_isize_as<T>(&mut self) -> Result<isize, EndianError>
+        where T: Endian + TryInto<isize>
+    {
+        self.track(|inner, order| inner.read_isize_as::<T>(order))
+    }
+
+    /// Fills `items` with values read in the configured byte order
+    pub fn read_into_slice<T: Endian>(&mut self, items: &mut [T]) -> Result<(), EndianError> {
+        self.track(|inner, order| {
+            if order.is_big() {
+                T::read_into_slice_be(items, inner)
+            } else {
+                T::read_into_slice_le(items, inner)
+            }
+        })
+    }
+
+    /// Reads a byte string preceded by its length as an `L` in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The bytes following the prefix
+    /// Err - [`EndianError::TooLong`] when the prefix exceeds `max_len`, or the stream error
+    pub fn read_bytes_prefixed<L>(&mut self, max_len: usize) -> Result<Vec<u8>, EndianError>
+        where L: Endian + TryInto<usize>
+    {
+        self.track(|inner, order| inner.read_bytes_prefixed::<L>(order, max_len))
+    }
+
+    /// Reads a UTF-8 string preceded by its length in bytes as an `L` in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The string following the prefix
+    /// Err - [`EndianError::TooLong`] when the prefix exceeds `max_len`, [`EndianError::InvalidUtf8`] when
+    /// the string is not valid UTF-8, or the stream error
+    pub fn read_string_prefixed<L>(&mut self, max_len: usize) -> Result<String, EndianError>
+        where L: Endian + TryInto<usize>
+    {
+        self.track(|inner, order| inner.read_string_prefixed::<L>(order, max_len))
+    }
+
+    /// The byte order values are read in
+    pub fn order(&self) -> B {
+        self.order
+    }
+
+    /// Changes the byte order of all later reads
+    pub fn set_order(&mut self, order: B) {
+        self.order = order;
+    }
+
+    /// The number of bytes read through the wrapper, reads made through [`get_mut`](EndianReader::get_mut)
+    /// are not counted
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Gets a reference to the inner reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner reader
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the wrapper, returning the inner reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Runs `f` on the inner reader, counting the bytes it reads and offsetting its errors by the
+    /// position it started at
+    fn track<T, F>(&mut self, f: F) -> Result<T, EndianError>
+        where F: FnOnce(&mut Counted<'_, R>, B) -> Result<T, EndianError>
+    {
+        let start = self.pos;
+        let mut inner = Counted { inner: &mut self.inner, count: &mut self.pos };
+        f(&mut inner, self.order).map_err(|e| e.offset_by(start))
+    }
+}
+
+/// Wraps an `io::Write`, writing every value in a configured byte order
+///
+/// # Remarks
+/// The byte order is either a runtime [`Endianness`] or one of the [`ByteOrder`] markers fixing it at
+/// compile time. Fields with a fixed byte order can still be written through
+/// [`get_mut`](EndianWriter::get_mut)
+///
+/// Counts the bytes written through it, errors report offsets from the position the wrapper was
+/// created at rather than from the start of the failing value
+#[derive(Debug)]
+pub struct EndianWriter<W, B = Endianness> {
+    inner: W,
+    order: B,
+    pos: usize
+}
+
+impl<W: Write, B: ByteOrder> EndianWriter<W, B> {
+    /// Creates a writer converting values into `order`
+    pub fn new(inner: W, order: B) -> Self {
+        EndianWriter {
+            inner,
+            order,
+            pos: 0
+        }
+    }
+
+    /// Writes a value in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - The error reported while writing the value
+    pub fn write<T: Endian>(&mut self, value: &T) -> Result<usize, EndianError> {
+        self.track(|inner, order| value.write(inner, order))
+    }
+
+    /// Writes a validated value such as a `bool` or `char` in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - The error reported while writing the value
+    pub fn try_write<T: TryEndian>(&mut self, value: &T) -> Result<usize, EndianError> {
+        self.track(|inner, order| TryEndian::write(value, inner, order))
+    }
+
+    /// Writes an enum as its tag and the payload of its variant in the configured byte order, see
+    /// [`Tagged`]
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written
+    /// Err - The error reported while writing the value
+    pub fn write_tagged<T: Tagged>(&mut self, value: &T) -> Result<usize, EndianError> {
+        self.track(|inner, order| value.write_tagged(inner, order))
+    }
+
+    /// Writes `value` as a `T` (e.g. `u32` or `u64`) in the configured byte order
+    /// 
+    /// # Returns
+    /// See [`WriteEndianExt::write_usize_as`]
+    pub fn write_usize_as<T>(&mut self, value: usize) -> Result<usize, EndianError>
+        where T: Endian + TryFrom<usize>
+    {
+        self.track(|inner, order| inner.write_usize_as::<T>(order, value))
+    }
+
+    /// Writes `value` as a `T` (e.g. `i32` or `i64`) in the configured byte order
+    /// 
+    /// # Returns
+    /// See [`WriteEndianExt::write_usize_as`]
+    pub fn write_isize_as<T>(&mut self, value: isize) -> Result<usize, EndianError>
+        where T: Endian + TryFrom<isize>
+    {
+        self.track(|inner, order| inner.write_isize_as::<T>(order, value))
+    }
+
+    /// Writes every element of `items` in the configured byte order
+    pub fn write_slice<T: Endian>(&mut self, items: &[T]) -> Result<usize, EndianError> {
+        self.track(|inner, order| {
+            if order.is_big() {
+                T::write_slice_be(items, inner)
+            } else {
+                T::write_slice_le(items, inner)
+            }
+        })
+    }
+
+    /// Writes `bytes` preceded by their length as an `L` in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written, including the prefix
+    /// Err - [`EndianError::Overflow`] when the length does not fit in an `L`, or the stream error
+    pub fn write_bytes_prefixed<L>(&mut self, bytes: &[u8]) -> Result<usize, EndianError>
+        where L: Endian + TryFrom<usize>
+    {
+        self.track(|inner, order| inner.write_bytes_prefixed::<L>(order, bytes))
+    }
+
+    /// Writes the UTF-8 bytes of `value` preceded by their length as an `L` in the configured byte order
+    /// 
+    /// # Returns
+    /// Ok - The number of bytes written, including the prefix
+    /// Err - [`EndianError::Overflow`] when the length does not fit in an `L`, or the stream error
+    pub fn write_str_prefixed<L>(&mut self, value: &str) -> Result<usize, EndianError>
+        where L: Endian + TryFrom<usize>
+    {
+        self.track(|inner, order| inner.write_str_prefixed::<L>(order, value))
+    }
+
+    /// The byte order values are written in
+    pub fn order(&self) -> B {
+        self.order
+    }
+
+    /// Changes the byte order of all later writes
+    pub fn set_order(&mut self, order: B) {
+        self.order = order;
+    }
+
+    /// The number of bytes written through the wrapper, writes made through
+    /// [`get_mut`](EndianWriter::get_mut) are not counted
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Gets a reference to the inner writer
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner writer
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes the wrapper, returning the inner writer
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Runs `f` on the inner writer, counting the bytes it writes and offsetting its errors by the
+    /// position it started at
+    fn track<T, F>(&mut self, f: F) -> Result<T, EndianError>
+        where F: FnOnce(&mut Counted<'_, W>, B) -> Result<T, EndianError>
+    {
+        let start = self.pos;
+        let mut inner = Counted { inner: &mut self.inner, count: &mut self.pos };
+        f(&mut inner, self.order).map_err(|e| e.offset_by(start))
+    }
+}
+
+/// Forwards to a stream while counting the bytes moved through it
+struct Counted<'a, S> {
+    inner: &'a mut S,
+    count: &'a mut usize
+}
+
+impl<R: Read> Read for Counted<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        *self.count = self.count.saturating_add(n);
+        Ok(n)
+    }
+}
+
+impl<W: Write> Write for Counted<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        *self.count = self.count.saturating_add(n);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
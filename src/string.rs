@@ -0,0 +1,72 @@
+//! Helpers shared by the string and byte string readers and writers
+
+use core::any;
+use core::convert::{TryFrom, TryInto};
+use core::ffi::CStr;
+use core::str;
+
+use crate::EndianError;
+
+/// Converts a decoded length prefix into a length in bytes
+///
+/// # Returns
+/// Ok - The length
+/// Err - [`EndianError::TooLong`] when the length exceeds `max_len`, or [`EndianError::Overflow`] when it
+/// does not fit in a `usize`
+pub(crate) fn decode_len<L: TryInto<usize>>(len: L, max_len: usize) -> Result<usize, EndianError> {
+    let len = len.try_into().map_err(|_| EndianError::overflow("usize"))?;
+    if len > max_len {
+        return Err(EndianError::TooLong { offset: 0, len, max: max_len });
+    }
+
+    Ok(len)
+}
+
+/// Converts a length in bytes into a length prefix of type `L`
+///
+/// # Returns
+/// Ok - The prefix
+/// Err - [`EndianError::Overflow`] when `len` does not fit in an `L`
+pub(crate) fn encode_len<L: TryFrom<usize>>(len: usize) -> Result<L, EndianError> {
+    L::try_from(len).map_err(|_| EndianError::overflow(any::type_name::<L>()))
+}
+
+/// Validates `bytes` as UTF-8
+pub(crate) fn decode_utf8(bytes: &[u8]) -> Result<&str, EndianError> {
+    str::from_utf8(bytes).map_err(|source| EndianError::InvalidUtf8 { offset: 0, source })
+}
+
+/// Finds the NUL terminated string at the start of `bytes`, scanning at most `max_len` bytes before the
+/// terminator
+///
+/// # Returns
+/// Ok - The string including its terminator, or None when `bytes` ends before a terminator is found
+/// Err - [`EndianError::TooLong`] when no terminator follows the first `max_len` bytes
+pub(crate) fn find_cstr(bytes: &[u8], max_len: usize) -> Result<Option<&CStr>, EndianError> {
+    let scan = &bytes[..bytes.len().min(max_len.saturating_add(1))];
+    match CStr::from_bytes_until_nul(scan) {
+        Ok(value) => Ok(Some(value)),
+        Err(_) if scan.len() > max_len => Err(too_long_unterminated(max_len)),
+        Err(_) => Ok(None)
+    }
+}
+
+/// Creates the error for a NUL terminated string with no terminator within `max_len` bytes
+pub(crate) fn too_long_unterminated(max_len: usize) -> EndianError {
+    EndianError::TooLong { offset: 0, len: max_len.saturating_add(1), max: max_len }
+}
+
+/// Checks that `len` bytes fit in a fixed width field of `width` bytes
+pub(crate) fn check_width(len: usize, width: usize) -> Result<(), EndianError> {
+    if len > width {
+        return Err(EndianError::TooLong { offset: 0, len, max: width });
+    }
+
+    Ok(())
+}
+
+/// Strips the trailing `pad` bytes from a fixed width field
+pub(crate) fn trim_padding(bytes: &[u8], pad: u8) -> &[u8] {
+    let len = bytes.iter().rposition(|&byte| byte != pad).map_or(0, |i| i + 1);
+    &bytes[..len]
+}
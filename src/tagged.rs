@@ -0,0 +1,93 @@
+//! Enums encoded as their tag followed by only the payload of their variant, through [`Tagged`]
+
+#[cfg(feature = "std")]
+use std::io::{Read, Write};
+
+#[cfg(feature = "std")]
+use crate::{read_full, write_full};
+use crate::{ByteOrder, EndianError};
+
+/// A trait for serializing enums as their tag followed by the fields of their variant, without padding
+///
+/// # Remarks
+/// Implemented by `#[derive(Endian)]` and `#[derive(TryEndian)]` on enums next to the fixed size
+/// encoding of those traits. The two are different wire formats: [`Endian`](crate::Endian) and
+/// [`TryEndian`](crate::TryEndian) pad every variant to the largest payload like a C tagged union, while
+/// this trait writes only the bytes of the variant, so messages of different sizes can follow each other
+/// in a stream. The tag is decoded first to find the length of the payload, which makes reads from a
+/// stream stop exactly at the end of the value
+///
+/// The `#[endian(unknown)]` variant has no payload, reading an unknown tag without one fails with
+/// [`EndianError::InvalidValue`]
+pub trait Tagged: Sized {
+    /// The number of bytes in the encoded tag
+    const TAG_SIZE: usize;
+
+    /// The number of bytes in the encoded representation of self, the tag included
+    fn tagged_len(&self) -> usize;
+
+    /// The number of payload bytes following `tag`, the first `Self::TAG_SIZE` bytes of an encoded value
+    /// stored in the byte order given by `order`
+    ///
+    /// # Returns
+    /// Ok - The length of the payload
+    /// Err - [`EndianError::EndOfStream`] when `tag` is shorter than `Self::TAG_SIZE` bytes, or
+    /// [`EndianError::InvalidValue`] when the tag does not match any variant
+    fn payload_len<B: ByteOrder>(tag: &[u8], order: B) -> Result<usize, EndianError>;
+
+    /// Convert self into the byte order given by `order` and write the results to the start of `out`
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written to `out`
+    /// Err - [`EndianError::EndOfStream`] when `out` is shorter than [`tagged_len`](Tagged::tagged_len)
+    fn write_tagged_into<B: ByteOrder>(&self, out: &mut [u8], order: B) -> Result<usize, EndianError>;
+
+    /// Creates self from the bytes at the start of `src` stored in the byte order given by `order`
+    ///
+    /// # Remarks
+    /// Bytes following the payload are ignored, the number of bytes used is given by
+    /// [`tagged_len`](Tagged::tagged_len) of the result
+    ///
+    /// # Returns
+    /// Ok with self
+    /// Err - [`EndianError::EndOfStream`] when `src` ends before the payload does, or
+    /// [`EndianError::InvalidValue`] when the tag does not match any variant
+    fn read_tagged_from<B: ByteOrder>(src: &[u8], order: B) -> Result<Self, EndianError>;
+
+    /// Convert self into the byte order given by `order` and write the results to `buf`
+    ///
+    /// # Remarks
+    /// The value is encoded into a temporary buffer which is written in one go
+    ///
+    /// # Returns
+    /// See [`Endian::write_le`](crate::Endian::write_le)
+    #[cfg(feature = "std")]
+    fn write_tagged<B: ByteOrder, W: Write>(&self, buf: &mut W, order: B) -> Result<usize, EndianError> {
+        let mut bytes = vec![0; self.tagged_len()];
+        self.write_tagged_into(&mut bytes, order)?;
+        write_full::<Self, W>(buf, &bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Creates self from the bytes in `buf` stored in the byte order given by `order`
+    ///
+    /// # Remarks
+    /// Reads the tag, then exactly the payload of its variant, so the bytes following the value are left
+    /// in `buf`
+    ///
+    /// # Returns
+    /// Ok with self
+    /// Err - [`EndianError::InvalidValue`] when the tag does not match any variant, otherwise see
+    /// [`Endian::read_le`](crate::Endian::read_le)
+    #[cfg(feature = "std")]
+    fn read_tagged<B: ByteOrder, R: Read>(buf: &mut R, order: B) -> Result<Self, EndianError> {
+        let mut bytes = vec![0; Self::TAG_SIZE];
+        read_full::<Self, R>(buf, &mut bytes)?;
+
+        let len = Self::payload_len(&bytes, order)?;
+        bytes.resize(Self::TAG_SIZE + len, 0);
+        read_full::<Self, R>(buf, &mut bytes[Self::TAG_SIZE..]).map_err(|e| e.offset_by(Self::TAG_SIZE))?;
+
+        Self::read_tagged_from(&bytes, order)
+    }
+}
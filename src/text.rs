@@ -0,0 +1,187 @@
+//! UTF-16 and UTF-32 text codecs
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use core::convert::TryInto;
+
+use crate::EndianError;
+
+/// A Unicode encoding made of code units wider than a byte
+///
+/// # Remarks
+/// Used by the `read_text_*` and `write_text_*` methods of the readers and writers, which take the byte
+/// order of the code units separately. Lengths and widths passed to those methods count code units
+/// rather than bytes
+///
+/// When decoding, a byte order mark at the start of the text overrides the given byte order and is
+/// removed from the result. Encoding never adds one, start the text with `'\u{FEFF}'` to write it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextEncoding {
+    /// UTF-16, 2 byte code units with characters outside the BMP stored as surrogate pairs
+    Utf16,
+    /// UTF-32, 4 byte code units each holding one character
+    Utf32
+}
+
+impl TextEncoding {
+    /// The number of bytes in a code unit
+    pub fn unit_size(self) -> usize {
+        match self {
+            TextEncoding::Utf16 => 2,
+            TextEncoding::Utf32 => 4
+        }
+    }
+
+    /// The number of code units needed to encode `value`
+    pub fn encoded_len(self, value: &str) -> usize {
+        match self {
+            TextEncoding::Utf16 => value.encode_utf16().count(),
+            TextEncoding::Utf32 => value.chars().count()
+        }
+    }
+
+    /// The name of the encoding
+    #[cfg(feature = "alloc")]
+    fn name(self) -> &'static str {
+        match self {
+            TextEncoding::Utf16 => "UTF-16",
+            TextEncoding::Utf32 => "UTF-32"
+        }
+    }
+
+    /// Reads the code unit at the start of `bytes`, which must hold at least one
+    #[cfg(feature = "alloc")]
+    fn unit(self, bytes: &[u8], big: bool) -> u32 {
+        match (self, big) {
+            (TextEncoding::Utf16, false) => u32::from(u16::from_le_bytes([bytes[0], bytes[1]])),
+            (TextEncoding::Utf16, true) => u32::from(u16::from_be_bytes([bytes[0], bytes[1]])),
+            (TextEncoding::Utf32, false) => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            (TextEncoding::Utf32, true) => u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+        }
+    }
+
+    /// Writes the code unit `unit` to the start of `out`, which must have room for one
+    fn put_unit(self, unit: u32, out: &mut [u8], big: bool) {
+        match (self, big) {
+            (TextEncoding::Utf16, false) => out[..2].copy_from_slice(&(unit as u16).to_le_bytes()),
+            (TextEncoding::Utf16, true) => out[..2].copy_from_slice(&(unit as u16).to_be_bytes()),
+            (TextEncoding::Utf32, false) => out[..4].copy_from_slice(&unit.to_le_bytes()),
+            (TextEncoding::Utf32, true) => out[..4].copy_from_slice(&unit.to_be_bytes())
+        }
+    }
+}
+
+/// Converts a length of `len` code units into a length in bytes
+///
+/// # Returns
+/// Ok - The length in bytes
+/// Err - [`EndianError::TooLong`] when `len` exceeds `max_len`, or [`EndianError::Overflow`] when the
+/// length does not fit in a `usize`
+pub(crate) fn byte_len<L: TryInto<usize>>(encoding: TextEncoding, len: L, max_len: usize) -> Result<usize, EndianError> {
+    let len = len.try_into().map_err(|_| EndianError::overflow("usize"))?;
+    if len > max_len {
+        return Err(too_long(encoding, len, max_len));
+    }
+
+    len.checked_mul(encoding.unit_size()).ok_or_else(|| EndianError::overflow("usize"))
+}
+
+/// Finds the zero code unit terminating the text at the start of `bytes`, scanning at most `max_len`
+/// code units before it
+///
+/// # Returns
+/// Ok - The number of code units before the terminator, or None when `bytes` ends before a terminator
+/// is found
+/// Err - [`EndianError::TooLong`] when no terminator follows the first `max_len` code units
+#[cfg(feature = "alloc")]
+pub(crate) fn find_nul(bytes: &[u8], encoding: TextEncoding, max_len: usize) -> Result<Option<usize>, EndianError> {
+    let size = encoding.unit_size();
+    let scan = bytes.chunks_exact(size).take(max_len.saturating_add(1));
+    let units = scan.len();
+    match scan.enumerate().find(|(_, unit)| unit.iter().all(|&byte| byte == 0)) {
+        Some((len, _)) => Ok(Some(len)),
+        None if units > max_len => Err(too_long(encoding, max_len.saturating_add(1), max_len)),
+        None => Ok(None)
+    }
+}
+
+/// Creates the error for text of `len` code units exceeding the limit of `max_len` code units
+pub(crate) fn too_long(encoding: TextEncoding, len: usize, max_len: usize) -> EndianError {
+    let size = encoding.unit_size();
+    EndianError::TooLong {
+        offset: 0,
+        len: len.saturating_mul(size),
+        max: max_len.saturating_mul(size)
+    }
+}
+
+/// Removes the trailing zero code units padding a fixed width field
+#[cfg(feature = "alloc")]
+pub(crate) fn trim_nul(bytes: &[u8], encoding: TextEncoding) -> &[u8] {
+    let size = encoding.unit_size();
+    let units = bytes.chunks_exact(size).rposition(|unit| unit.iter().any(|&byte| byte != 0));
+    &bytes[..units.map_or(0, |i| (i + 1) * size)]
+}
+
+/// Decodes the code units in `bytes`, which hold a whole number of them, honoring a leading byte
+/// order mark
+///
+/// # Returns
+/// Ok - The decoded text
+/// Err - [`EndianError::InvalidUnicode`] at the first unpaired surrogate or invalid code point
+#[cfg(feature = "alloc")]
+pub(crate) fn decode(bytes: &[u8], encoding: TextEncoding, big: bool) -> Result<String, EndianError> {
+    let size = encoding.unit_size();
+    let bom = match (encoding, &bytes[..size.min(bytes.len())]) {
+        (TextEncoding::Utf16, [0xfe, 0xff]) | (TextEncoding::Utf32, [0, 0, 0xfe, 0xff]) => Some(true),
+        (TextEncoding::Utf16, [0xff, 0xfe]) | (TextEncoding::Utf32, [0xff, 0xfe, 0, 0]) => Some(false),
+        _ => None
+    };
+
+    let start = if bom.is_some() { size } else { 0 };
+    let big = bom.unwrap_or(big);
+    let units = bytes[start..].chunks_exact(size).map(|unit| encoding.unit(unit, big));
+    let invalid = |index: usize, unit: u32| EndianError::InvalidUnicode {
+        offset: start + index * size,
+        encoding: encoding.name(),
+        unit
+    };
+
+    let mut text = String::with_capacity(bytes.len() / size);
+    match encoding {
+        TextEncoding::Utf16 => {
+            let mut index = 0;
+            for c in char::decode_utf16(units.map(|unit| unit as u16)) {
+                let c = c.map_err(|e| invalid(index, u32::from(e.unpaired_surrogate())))?;
+                index += c.len_utf16();
+                text.push(c);
+            }
+        },
+        TextEncoding::Utf32 => {
+            for (index, unit) in units.enumerate() {
+                text.push(char::from_u32(unit).ok_or_else(|| invalid(index, unit))?);
+            }
+        }
+    }
+
+    Ok(text)
+}
+
+/// Encodes `value` into `out`, which must be exactly `encoding.encoded_len(value)` code units long
+pub(crate) fn encode_into(value: &str, encoding: TextEncoding, big: bool, out: &mut [u8]) {
+    let size = encoding.unit_size();
+    let mut units = out.chunks_exact_mut(size);
+    match encoding {
+        TextEncoding::Utf16 => {
+            for (unit, dst) in value.encode_utf16().zip(&mut units) {
+                encoding.put_unit(u32::from(unit), dst, big);
+            }
+        },
+        TextEncoding::Utf32 => {
+            for (c, dst) in value.chars().zip(&mut units) {
+                encoding.put_unit(u32::from(c), dst, big);
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Integers stored in any whole number of bytes up to 8, e.g. 24 bit PCM samples or 48 bit ids
+
+use core::any;
+use core::convert::TryInto;
+
+use crate::EndianError;
+
+const UINT_NAMES: [&str; 8] = ["u8", "u16", "u24", "u32", "u40", "u48", "u56", "u64"];
+const INT_NAMES: [&str; 8] = ["i8", "i16", "i24", "i32", "i40", "i48", "i56", "i64"];
+
+/// Panics unless `nbytes` is a supported width
+#[inline]
+pub(crate) fn check_width(nbytes: usize) {
+    assert!((1..=8).contains(&nbytes), "integer width must be between 1 and 8 bytes");
+}
+
+/// Decodes the unsigned integer stored in `bytes`, which must be 1 to 8 bytes long
+pub(crate) fn decode_uint(bytes: &[u8], big: bool) -> u64 {
+    let mut buf = [0; 8];
+    if big {
+        buf[8 - bytes.len()..].copy_from_slice(bytes);
+        u64::from_be_bytes(buf)
+    } else {
+        buf[..bytes.len()].copy_from_slice(bytes);
+        u64::from_le_bytes(buf)
+    }
+}
+
+/// Decodes the two's complement integer stored in `bytes`, which must be 1 to 8 bytes long
+pub(crate) fn decode_int(bytes: &[u8], big: bool) -> i64 {
+    let shift = 64 - bytes.len() * 8;
+    ((decode_uint(bytes, big) << shift) as i64) >> shift
+}
+
+/// Converts `value` into a `U`, e.g. a `usize` into the fixed width integer it is stored as
+///
+/// # Returns
+/// Ok - The converted value
+/// Err - [`EndianError::Overflow`] when `value` does not fit in a `U`
+pub(crate) fn convert<T: TryInto<U>, U>(value: T) -> Result<U, EndianError> {
+    value.try_into().map_err(|_| EndianError::overflow(any::type_name::<U>()))
+}
+
+/// Encodes `value` as an unsigned integer of `nbytes` bytes
+///
+/// # Returns
+/// Ok - A buffer whose first `nbytes` bytes hold the encoded value
+/// Err - [`EndianError::Overflow`] when `value` does not fit in `nbytes` bytes
+pub(crate) fn encode_uint(value: u64, nbytes: usize, big: bool) -> Result<[u8; 8], EndianError> {
+    check_width(nbytes);
+    if nbytes < 8 && value >> (nbytes * 8) != 0 {
+        return Err(EndianError::overflow(UINT_NAMES[nbytes - 1]));
+    }
+
+    Ok(pack(value, nbytes, big))
+}
+
+/// Encodes `value` as a two's complement integer of `nbytes` bytes
+///
+/// # Returns
+/// Ok - A buffer whose first `nbytes` bytes hold the encoded value
+/// Err - [`EndianError::Overflow`] when `value` does not fit in `nbytes` bytes
+pub(crate) fn encode_int(value: i64, nbytes: usize, big: bool) -> Result<[u8; 8], EndianError> {
+    check_width(nbytes);
+
+    // Every bit above the sign bit of the narrow form must be a copy of it
+    let high = value >> (nbytes * 8 - 1);
+    if high != 0 && high != -1 {
+        return Err(EndianError::overflow(INT_NAMES[nbytes - 1]));
+    }
+
+    Ok(pack(value as u64, nbytes, big))
+}
+
+/// Moves the low `nbytes` bytes of `value` to the front of the buffer in the requested byte order
+fn pack(value: u64, nbytes: usize, big: bool) -> [u8; 8] {
+    if big {
+        (value << (64 - nbytes * 8)).to_be_bytes()
+    } else {
+        value.to_le_bytes()
+    }
+}
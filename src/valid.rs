@@ -0,0 +1,347 @@
+//! Types with bit patterns which are not valid values, decoded through [`TryEndian`]
+
+use core::num::{NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8};
+use core::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8};
+#[cfg(feature = "std")]
+use std::io::{Read, Write};
+
+#[cfg(feature = "std")]
+use crate::{read_full, write_full};
+use crate::{ByteOrder, Endian, EndianError};
+
+/// A trait for serializing types which can not be created from arbitrary bytes
+///
+/// # Remarks
+/// Mirrors [`Endian`] but every read validates the decoded bytes and fails with
+/// [`EndianError::InvalidValue`] instead of producing an invalid value. Implemented for `bool` (which
+/// only accepts `0` and `1`), `char` (stored as a `u32` code point, rejecting surrogates and values above
+/// `char::MAX`) and the `NonZero*` integers (rejecting zero)
+///
+/// `Option<NonZero*>` accepts every bit pattern, with zero decoding to `None`, so it implements
+/// [`Endian`] instead and can be used anywhere a plain integer can
+pub trait TryEndian: Sized {
+    /// The number of bytes in the encoded representation of the type
+    const SIZE: usize;
+
+    /// Convert self into little endian representation and write the results to `buf`
+    ///
+    /// # Remarks
+    /// The default implementation encodes into a temporary buffer with
+    /// [`write_le_unchecked`](TryEndian::write_le_unchecked), like [`Endian::write_le`]
+    ///
+    /// # Returns
+    /// See [`Endian::write_le`]
+    #[cfg(feature = "std")]
+    fn write_le<W: Write>(&self, buf: &mut W) -> Result<usize, EndianError> {
+        let mut bytes = vec![0; Self::SIZE];
+        unsafe { self.write_le_unchecked(bytes.as_mut_ptr()); }
+        write_full::<Self, W>(buf, &bytes)?;
+        Ok(Self::SIZE)
+    }
+
+    /// Convert self into little endian representation and write the results to `out`
+    ///
+    /// # Safety
+    /// Assumes that `out` is valid for writes of `Self::SIZE` bytes, `out` does not need to be aligned
+    ///
+    /// # Returns
+    /// The number of bytes written to `out`
+    unsafe fn write_le_unchecked(&self, out: *mut u8) -> usize;
+
+    /// Convert self into big endian representation and write the results to `buf`
+    ///
+    /// # Remarks
+    /// See [`write_le`](TryEndian::write_le)
+    ///
+    /// # Returns
+    /// See [`Endian::write_be`]
+    #[cfg(feature = "std")]
+    fn write_be<W: Write>(&self, buf: &mut W) -> Result<usize, EndianError> {
+        let mut bytes = vec![0; Self::SIZE];
+        unsafe { self.write_be_unchecked(bytes.as_mut_ptr()); }
+        write_full::<Self, W>(buf, &bytes)?;
+        Ok(Self::SIZE)
+    }
+
+    /// Convert self into big endian representation and write the results to `out`
+    ///
+    /// # Safety
+    /// Assumes that `out` is valid for writes of `Self::SIZE` bytes, `out` does not need to be aligned
+    ///
+    /// # Returns
+    /// The number of bytes written to `out`
+    unsafe fn write_be_unchecked(&self, out: *mut u8) -> usize;
+
+    /// Creates self from the little endian bytes in `buf`
+    ///
+    /// # Remarks
+    /// The default implementation reads into a temporary buffer and decodes it with
+    /// [`try_read_le_unchecked`](TryEndian::try_read_le_unchecked), like [`Endian::read_le`]
+    ///
+    /// # Returns
+    /// Ok with self
+    /// Err - [`EndianError::InvalidValue`] when the bytes are not a valid value, otherwise see
+    /// [`Endian::read_le`]
+    #[cfg(feature = "std")]
+    fn try_read_le<R: Read>(buf: &mut R) -> Result<Self, EndianError> {
+        let mut bytes = vec![0; Self::SIZE];
+        read_full::<Self, R>(buf, &mut bytes)?;
+        unsafe { Self::try_read_le_unchecked(bytes.as_ptr()) }
+    }
+
+    /// Creates self from the little endian bytes at `src`
+    ///
+    /// # Safety
+    /// Assumes that `src` is valid for reads of `Self::SIZE` bytes, `src` does not need to be aligned
+    ///
+    /// # Returns
+    /// Ok with self
+    /// Err - [`EndianError::InvalidValue`] when the bytes are not a valid value
+    unsafe fn try_read_le_unchecked(src: *const u8) -> Result<Self, EndianError>;
+
+    /// Creates self from the big endian bytes in `buf`
+    ///
+    /// # Remarks
+    /// See [`try_read_le`](TryEndian::try_read_le)
+    ///
+    /// # Returns
+    /// Ok with self
+    /// Err - [`EndianError::InvalidValue`] when the bytes are not a valid value, otherwise see
+    /// [`Endian::read_be`]
+    #[cfg(feature = "std")]
+    fn try_read_be<R: Read>(buf: &mut R) -> Result<Self, EndianError> {
+        let mut bytes = vec![0; Self::SIZE];
+        read_full::<Self, R>(buf, &mut bytes)?;
+        unsafe { Self::try_read_be_unchecked(bytes.as_ptr()) }
+    }
+
+    /// Creates self from the big endian bytes at `src`
+    ///
+    /// # Safety
+    /// Assumes that `src` is valid for reads of `Self::SIZE` bytes, `src` does not need to be aligned
+    ///
+    /// # Returns
+    /// Ok with self
+    /// Err - [`EndianError::InvalidValue`] when the bytes are not a valid value
+    unsafe fn try_read_be_unchecked(src: *const u8) -> Result<Self, EndianError>;
+
+    /// Convert self into little endian representation and write the results to the start of `out`
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written to `out`
+    /// Err - [`EndianError::EndOfStream`] when `out` is shorter than `Self::SIZE` bytes
+    fn write_le_into(&self, out: &mut [u8]) -> Result<usize, EndianError> {
+        if out.len() < Self::SIZE {
+            return Err(EndianError::EndOfStream(0));
+        }
+
+        Ok(unsafe { self.write_le_unchecked(out.as_mut_ptr()) })
+    }
+
+    /// Convert self into big endian representation and write the results to the start of `out`
+    ///
+    /// # Returns
+    /// Ok - The number of bytes written to `out`
+    /// Err - [`EndianError::EndOfStream`] when `out` is shorter than `Self::SIZE` bytes
+    fn write_be_into(&self, out: &mut [u8]) -> Result<usize, EndianError> {
+        if out.len() < Self::SIZE {
+            return Err(EndianError::EndOfStream(0));
+        }
+
+        Ok(unsafe { self.write_be_unchecked(out.as_mut_ptr()) })
+    }
+
+    /// Creates self from the little endian bytes at the start of `src`
+    ///
+    /// # Returns
+    /// Ok with self
+    /// Err - [`EndianError::EndOfStream`] when `src` is shorter than `Self::SIZE` bytes, or
+    /// [`EndianError::InvalidValue`] when the bytes are not a valid value
+    fn try_read_le_from(src: &[u8]) -> Result<Self, EndianError> {
+        if src.len() < Self::SIZE {
+            return Err(EndianError::EndOfStream(0));
+        }
+
+        unsafe { Self::try_read_le_unchecked(src.as_ptr()) }
+    }
+
+    /// Creates self from the big endian bytes at the start of `src`
+    ///
+    /// # Returns
+    /// Ok with self
+    /// Err - [`EndianError::EndOfStream`] when `src` is shorter than `Self::SIZE` bytes, or
+    /// [`EndianError::InvalidValue`] when the bytes are not a valid value
+    fn try_read_be_from(src: &[u8]) -> Result<Self, EndianError> {
+        if src.len() < Self::SIZE {
+            return Err(EndianError::EndOfStream(0));
+        }
+
+        unsafe { Self::try_read_be_unchecked(src.as_ptr()) }
+    }
+
+    /// Convert self into the byte order given by `order` and write the results to `buf`
+    ///
+    /// # Returns
+    /// See [`write_le`](TryEndian::write_le)
+    #[cfg(feature = "std")]
+    fn write<B: ByteOrder, W: Write>(&self, buf: &mut W, order: B) -> Result<usize, EndianError> {
+        if order.is_big() {
+            self.write_be(buf)
+        } else {
+            self.write_le(buf)
+        }
+    }
+
+    /// Creates self from the bytes in `buf` stored in the byte order given by `order`
+    ///
+    /// # Returns
+    /// See [`try_read_le`](TryEndian::try_read_le)
+    #[cfg(feature = "std")]
+    fn try_read<B: ByteOrder, R: Read>(buf: &mut R, order: B) -> Result<Self, EndianError> {
+        if order.is_big() {
+            Self::try_read_be(buf)
+        } else {
+            Self::try_read_le(buf)
+        }
+    }
+
+    /// Convert self into the byte order given by `order` and write the results to the start of `out`
+    ///
+    /// # Returns
+    /// See [`write_le_into`](TryEndian::write_le_into)
+    fn write_into<B: ByteOrder>(&self, out: &mut [u8], order: B) -> Result<usize, EndianError> {
+        if order.is_big() {
+            self.write_be_into(out)
+        } else {
+            self.write_le_into(out)
+        }
+    }
+
+    /// Creates self from the bytes at the start of `src` stored in the byte order given by `order`
+    ///
+    /// # Returns
+    /// See [`try_read_le_from`](TryEndian::try_read_le_from)
+    fn try_read_from<B: ByteOrder>(src: &[u8], order: B) -> Result<Self, EndianError> {
+        if order.is_big() {
+            Self::try_read_be_from(src)
+        } else {
+            Self::try_read_le_from(src)
+        }
+    }
+}
+
+// Each type is stored as a raw `Endian` value, `$from` validates it and `$to` converts back
+macro_rules! impl_try_endian {
+    ($($ty:ty: $raw:ty, $from:expr, $to:expr;)*) => {
+        $(
+            impl TryEndian for $ty {
+                const SIZE: usize = <$raw as Endian>::SIZE;
+
+                #[cfg(feature = "std")]
+                fn write_le<W: Write>(&self, buf: &mut W) -> Result<usize, EndianError> {
+                    Endian::write_le(&($to)(self), buf)
+                }
+
+                unsafe fn write_le_unchecked(&self, out: *mut u8) -> usize {
+                    Endian::write_le_unchecked(&($to)(self), out)
+                }
+
+                #[cfg(feature = "std")]
+                fn write_be<W: Write>(&self, buf: &mut W) -> Result<usize, EndianError> {
+                    Endian::write_be(&($to)(self), buf)
+                }
+
+                unsafe fn write_be_unchecked(&self, out: *mut u8) -> usize {
+                    Endian::write_be_unchecked(&($to)(self), out)
+                }
+
+                #[cfg(feature = "std")]
+                fn try_read_le<R: Read>(buf: &mut R) -> Result<Self, EndianError> {
+                    let raw = <$raw as Endian>::read_le(buf)?;
+                    ($from)(raw).ok_or_else(|| EndianError::invalid_value(stringify!($ty), &raw.to_le_bytes()))
+                }
+
+                unsafe fn try_read_le_unchecked(src: *const u8) -> Result<Self, EndianError> {
+                    let raw = <$raw as Endian>::read_le_unchecked(src);
+                    ($from)(raw).ok_or_else(|| EndianError::invalid_value(stringify!($ty), &raw.to_le_bytes()))
+                }
+
+                #[cfg(feature = "std")]
+                fn try_read_be<R: Read>(buf: &mut R) -> Result<Self, EndianError> {
+                    let raw = <$raw as Endian>::read_be(buf)?;
+                    ($from)(raw).ok_or_else(|| EndianError::invalid_value(stringify!($ty), &raw.to_be_bytes()))
+                }
+
+                unsafe fn try_read_be_unchecked(src: *const u8) -> Result<Self, EndianError> {
+                    let raw = <$raw as Endian>::read_be_unchecked(src);
+                    ($from)(raw).ok_or_else(|| EndianError::invalid_value(stringify!($ty), &raw.to_be_bytes()))
+                }
+            }
+        )*
+    };
+}
+
+impl_try_endian! {
+    bool: u8, |raw| match raw { 0 => Some(false), 1 => Some(true), _ => None }, |value: &bool| u8::from(*value);
+    char: u32, char::from_u32, |value: &char| u32::from(*value);
+    NonZeroU8: u8, NonZeroU8::new, |value: &NonZeroU8| value.get();
+    NonZeroU16: u16, NonZeroU16::new, |value: &NonZeroU16| value.get();
+    NonZeroU32: u32, NonZeroU32::new, |value: &NonZeroU32| value.get();
+    NonZeroU64: u64, NonZeroU64::new, |value: &NonZeroU64| value.get();
+    NonZeroU128: u128, NonZeroU128::new, |value: &NonZeroU128| value.get();
+    NonZeroI8: i8, NonZeroI8::new, |value: &NonZeroI8| value.get();
+    NonZeroI16: i16, NonZeroI16::new, |value: &NonZeroI16| value.get();
+    NonZeroI32: i32, NonZeroI32::new, |value: &NonZeroI32| value.get();
+    NonZeroI64: i64, NonZeroI64::new, |value: &NonZeroI64| value.get();
+    NonZeroI128: i128, NonZeroI128::new, |value: &NonZeroI128| value.get();
+}
+
+// Zero is the niche used for `None`, so every bit pattern decodes to a valid option
+macro_rules! impl_option_non_zero {
+    ($($ty:ident: $raw:ty),*) => {
+        $(
+            impl Endian for Option<$ty> {
+                const SIZE: usize = <$raw as Endian>::SIZE;
+
+                #[cfg(feature = "std")]
+                fn write_le<W: Write>(&self, buf: &mut W) -> Result<usize, EndianError> {
+                    self.map_or(0, $ty::get).write_le(buf)
+                }
+
+                unsafe fn write_le_unchecked(&self, out: *mut u8) -> usize {
+                    self.map_or(0, $ty::get).write_le_unchecked(out)
+                }
+
+                #[cfg(feature = "std")]
+                fn write_be<W: Write>(&self, buf: &mut W) -> Result<usize, EndianError> {
+                    self.map_or(0, $ty::get).write_be(buf)
+                }
+
+                unsafe fn write_be_unchecked(&self, out: *mut u8) -> usize {
+                    self.map_or(0, $ty::get).write_be_unchecked(out)
+                }
+
+                #[cfg(feature = "std")]
+                fn read_le<R: Read>(buf: &mut R) -> Result<Self, EndianError> {
+                    <$raw as Endian>::read_le(buf).map($ty::new)
+                }
+
+                unsafe fn read_le_unchecked(src: *const u8) -> Self {
+                    $ty::new(<$raw as Endian>::read_le_unchecked(src))
+                }
+
+                #[cfg(feature = "std")]
+                fn read_be<R: Read>(buf: &mut R) -> Result<Self, EndianError> {
+                    <$raw as Endian>::read_be(buf).map($ty::new)
+                }
+
+                unsafe fn read_be_unchecked(src: *const u8) -> Self {
+                    $ty::new(<$raw as Endian>::read_be_unchecked(src))
+                }
+            }
+        )*
+    };
+}
+
+impl_option_non_zero!(NonZeroU8: u8, NonZeroU16: u16, NonZeroU32: u32, NonZeroU64: u64, NonZeroU128: u128);
+impl_option_non_zero!(NonZeroI8: i8, NonZeroI16: i16, NonZeroI32: i32, NonZeroI64: i64, NonZeroI128: i128);
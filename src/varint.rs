@@ -0,0 +1,331 @@
+//! Variable length integer encodings
+//!
+//! Each encoding is a zero sized marker implementing [`Varint`], values are read and written through
+//! the `read_varint`/`write_varint` methods of the readers and writers, e.g.
+//! `reader.read_varint::<Leb128>()`
+
+use crate::EndianError;
+
+/// The largest number of bytes used by any of the encodings
+pub(crate) const MAX_VARINT_LEN: usize = 10;
+
+/// A variable length integer encoding
+///
+/// # Remarks
+/// Decoding rejects values which overflow [`Value`](Varint::Value) with [`EndianError::Overflow`]
+/// and, unless the encoding explicitly allows it, encodings using more bytes than necessary with
+/// [`EndianError::Overlong`]
+///
+/// This trait is sealed and can not be implemented outside of this crate
+pub trait Varint: private::Sealed {
+    /// The type of the decoded value
+    type Value: Copy;
+
+    /// Decodes a value from the bytes returned by `next`
+    #[doc(hidden)]
+    fn decode<F>(next: F) -> Result<Self::Value, EndianError>
+        where F: FnMut() -> Result<u8, EndianError>;
+
+    /// Encodes `value` into `out`, returning the number of bytes used
+    #[doc(hidden)]
+    fn encode(value: Self::Value, out: &mut [u8; MAX_VARINT_LEN]) -> Result<usize, EndianError>;
+
+    /// Whether encodings longer than the minimal one are rejected
+    #[doc(hidden)]
+    const CANONICAL: bool = true;
+}
+
+/// Unsigned LEB128, little endian groups of 7 bits decoded into a `u64`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Leb128;
+
+/// Signed LEB128, little endian groups of 7 bits sign extended into an `i64`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Sleb128;
+
+/// Protobuf style zigzag varint, an `i64` mapped onto an unsigned value and stored as LEB128
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ZigZag;
+
+/// QUIC variable length integer (RFC 9000), 1, 2, 4 or 8 big endian bytes holding up to 62 bits
+///
+/// # Remarks
+/// QUIC allows values to be encoded with more bytes than necessary so those are accepted while
+/// decoding, encoding always picks the shortest form
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QuicVarint;
+
+/// SQLite varint, 1 to 9 big endian bytes where the 9th byte contributes all 8 of its bits
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SqliteVarint;
+
+/// Variable length quantity as used by MIDI, big endian groups of 7 bits decoded into a `u64`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Vlq;
+
+impl Varint for Leb128 {
+    type Value = u64;
+
+    fn decode<F>(mut next: F) -> Result<u64, EndianError>
+        where F: FnMut() -> Result<u8, EndianError>
+    {
+        let mut value = 0;
+        for i in 0..MAX_VARINT_LEN {
+            let byte = next()?;
+
+            // Only the lowest bit of the 10th byte still fits in a u64
+            if i == MAX_VARINT_LEN - 1 && byte > 1 {
+                break;
+            }
+
+            value |= u64::from(byte & 0x7f) << (i * 7);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(EndianError::overflow("LEB128"))
+    }
+
+    fn encode(mut value: u64, out: &mut [u8; MAX_VARINT_LEN]) -> Result<usize, EndianError> {
+        let mut len = 0;
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                out[len] = byte;
+                return Ok(len + 1);
+            }
+
+            out[len] = byte | 0x80;
+            len += 1;
+        }
+    }
+}
+
+impl Varint for Sleb128 {
+    type Value = i64;
+
+    fn decode<F>(mut next: F) -> Result<i64, EndianError>
+        where F: FnMut() -> Result<u8, EndianError>
+    {
+        let mut value = 0;
+        for i in 0..MAX_VARINT_LEN {
+            let byte = next()?;
+
+            // The 10th byte only holds the sign bit, so it must be a plain sign extension
+            if i == MAX_VARINT_LEN - 1 && byte != 0x00 && byte != 0x7f {
+                break;
+            }
+
+            value |= i64::from(byte & 0x7f) << (i * 7);
+            if byte & 0x80 == 0 {
+                let shift = (i + 1) * 7;
+                if shift < 64 && byte & 0x40 != 0 {
+                    value |= -1 << shift;
+                }
+
+                return Ok(value);
+            }
+        }
+
+        Err(EndianError::overflow("SLEB128"))
+    }
+
+    fn encode(mut value: i64, out: &mut [u8; MAX_VARINT_LEN]) -> Result<usize, EndianError> {
+        let mut len = 0;
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+
+            // Done once the remaining bits are a sign extension of the group just written
+            let sign = byte & 0x40 != 0;
+            if (value == 0 && !sign) || (value == -1 && sign) {
+                out[len] = byte;
+                return Ok(len + 1);
+            }
+
+            out[len] = byte | 0x80;
+            len += 1;
+        }
+    }
+}
+
+impl Varint for ZigZag {
+    type Value = i64;
+
+    fn decode<F>(next: F) -> Result<i64, EndianError>
+        where F: FnMut() -> Result<u8, EndianError>
+    {
+        let value = Leb128::decode(next)?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    fn encode(value: i64, out: &mut [u8; MAX_VARINT_LEN]) -> Result<usize, EndianError> {
+        Leb128::encode(((value << 1) ^ (value >> 63)) as u64, out)
+    }
+}
+
+impl QuicVarint {
+    /// The largest value which can be encoded
+    pub const MAX: u64 = (1 << 62) - 1;
+}
+
+impl Varint for QuicVarint {
+    type Value = u64;
+
+    const CANONICAL: bool = false;
+
+    fn decode<F>(mut next: F) -> Result<u64, EndianError>
+        where F: FnMut() -> Result<u8, EndianError>
+    {
+        // The top two bits of the first byte hold the base 2 logarithm of the length
+        let first = next()?;
+        let len = 1 << (first >> 6);
+
+        let mut value = u64::from(first & 0x3f);
+        for _ in 1..len {
+            value = (value << 8) | u64::from(next()?);
+        }
+
+        Ok(value)
+    }
+
+    fn encode(value: u64, out: &mut [u8; MAX_VARINT_LEN]) -> Result<usize, EndianError> {
+        let (len, tag) = match value {
+            0..=0x3f => (1, 0x00),
+            0x40..=0x3fff => (2, 0x40),
+            0x4000..=0x3fff_ffff => (4, 0x80),
+            0x4000_0000..=QuicVarint::MAX => (8, 0xc0),
+            _ => return Err(EndianError::overflow("QUIC varint"))
+        };
+
+        out[..len].copy_from_slice(&value.to_be_bytes()[8 - len..]);
+        out[0] |= tag;
+        Ok(len)
+    }
+}
+
+impl Varint for SqliteVarint {
+    type Value = u64;
+
+    fn decode<F>(mut next: F) -> Result<u64, EndianError>
+        where F: FnMut() -> Result<u8, EndianError>
+    {
+        let mut value = 0;
+        for _ in 0..8 {
+            let byte = next()?;
+            value = (value << 7) | u64::from(byte & 0x7f);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        // The 9th byte contributes all 8 bits, which always fits in a u64
+        Ok((value << 8) | u64::from(next()?))
+    }
+
+    fn encode(value: u64, out: &mut [u8; MAX_VARINT_LEN]) -> Result<usize, EndianError> {
+        // Values above 56 bits need the 9 byte form
+        if value >> 56 != 0 {
+            let high = value >> 8;
+            for (i, byte) in out[..8].iter_mut().enumerate() {
+                *byte = ((high >> (49 - i * 7)) & 0x7f) as u8 | 0x80;
+            }
+
+            out[8] = value as u8;
+            return Ok(9);
+        }
+
+        Ok(encode_groups_be(value, out))
+    }
+}
+
+impl Varint for Vlq {
+    type Value = u64;
+
+    fn decode<F>(mut next: F) -> Result<u64, EndianError>
+        where F: FnMut() -> Result<u8, EndianError>
+    {
+        let mut value: u64 = 0;
+        for _ in 0..MAX_VARINT_LEN {
+            let byte = next()?;
+
+            // Shifting in another group must not push set bits out of the top
+            if value >> 57 != 0 {
+                break;
+            }
+
+            value = (value << 7) | u64::from(byte & 0x7f);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(EndianError::overflow("VLQ"))
+    }
+
+    fn encode(value: u64, out: &mut [u8; MAX_VARINT_LEN]) -> Result<usize, EndianError> {
+        Ok(encode_groups_be(value, out))
+    }
+}
+
+/// Encodes `value` as big endian groups of 7 bits with the high bit set on every byte but the last
+fn encode_groups_be(value: u64, out: &mut [u8; MAX_VARINT_LEN]) -> usize {
+    let bits = 64 - value.leading_zeros() as usize;
+    let len = bits.div_ceil(7).max(1);
+    for (i, byte) in out[..len].iter_mut().enumerate() {
+        let shift = (len - i - 1) * 7;
+        *byte = ((value >> shift) & 0x7f) as u8 | 0x80;
+    }
+
+    out[len - 1] &= 0x7f;
+    len
+}
+
+/// Decodes a value of encoding `E` from the bytes returned by `next`, which is given the index of the
+/// byte within the value
+///
+/// # Returns
+/// Ok - The value and the number of bytes it used
+/// Err - When `next` fails or the encoding is malformed, offsets are relative to the start of the value
+pub(crate) fn decode<E, F>(mut next: F) -> Result<(E::Value, usize), EndianError>
+    where E: Varint,
+          F: FnMut(usize) -> Result<u8, EndianError>
+{
+    let mut len = 0;
+    let value = E::decode(|| {
+        let byte = next(len)?;
+        len += 1;
+        Ok(byte)
+    })?;
+
+    // A canonical value must re-encode to the same number of bytes
+    if E::CANONICAL && encode::<E>(value)?.1 != len {
+        return Err(EndianError::Overlong { offset: 0 });
+    }
+
+    Ok((value, len))
+}
+
+/// Encodes `value` with encoding `E`
+///
+/// # Returns
+/// Ok - A buffer holding the encoded bytes and their number
+/// Err - When `value` can not be represented by the encoding
+pub(crate) fn encode<E: Varint>(value: E::Value) -> Result<([u8; MAX_VARINT_LEN], usize), EndianError> {
+    let mut buf = [0; MAX_VARINT_LEN];
+    let len = E::encode(value, &mut buf)?;
+    Ok((buf, len))
+}
+
+mod private {
+    pub trait Sealed { }
+
+    impl Sealed for super::Leb128 { }
+    impl Sealed for super::Sleb128 { }
+    impl Sealed for super::ZigZag { }
+    impl Sealed for super::QuicVarint { }
+    impl Sealed for super::SqliteVarint { }
+    impl Sealed for super::Vlq { }
+}
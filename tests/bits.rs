@@ -0,0 +1,170 @@
+use tinybit::{BitOrder, BitReader, BitWriter, EndianError, LsbFirst, MsbFirst};
+
+/// Field widths chosen so fields straddle the 64 bit refill and emit boundaries
+const FIELDS: [(u64, u32); 9] = [
+    (1, 1),
+    (0b101, 3),
+    (0x5555_5555_5555_5555 >> 1, 63),
+    (0xDEAD_BEEF_0BAD_F00D, 64),
+    (0, 1),
+    (u64::MAX, 64),
+    (0b010, 3),
+    (1 << 62, 63),
+    (0x0123_4567_89AB_CDEF, 64)
+];
+
+/// Writes every field of `FIELDS` in `order` and reads them back
+fn round_trip<O: BitOrder>(order: O) {
+    let mut buf = [0; 64];
+    let mut writer = BitWriter::with_order(&mut buf[..], order);
+    for &(value, count) in &FIELDS {
+        writer.write_bits(value, count).unwrap();
+    }
+
+    let bits: u64 = FIELDS.iter().map(|&(_, count)| u64::from(count)).sum();
+    assert_eq!(writer.bit_position(), bits);
+    writer.flush().unwrap();
+    assert_eq!(writer.bit_position(), bits.div_ceil(8) * 8);
+
+    let mut reader = BitReader::with_order(&buf[..], order);
+    for &(value, count) in &FIELDS {
+        assert_eq!(reader.read_bits(count).unwrap(), value, "field of {} bits", count);
+    }
+    assert_eq!(reader.bit_position(), bits);
+}
+
+#[test]
+fn msb_first_round_trip() {
+    round_trip(MsbFirst);
+}
+
+#[test]
+fn lsb_first_round_trip() {
+    round_trip(LsbFirst);
+}
+
+#[test]
+fn bits_are_packed_in_order() {
+    let mut buf = [0; 2];
+    let mut writer = BitWriter::new(&mut buf[..]);
+    writer.write_bit(true).unwrap();
+    writer.write_bits(0b101, 3).unwrap();
+    writer.write_bits(0x3F, 6).unwrap();
+    writer.finish().unwrap();
+    assert_eq!(buf, [0b1101_1111, 0b1100_0000]);
+
+    let mut buf = [0; 2];
+    let mut writer = BitWriter::with_order(&mut buf[..], LsbFirst);
+    writer.write_bit(true).unwrap();
+    writer.write_bits(0b101, 3).unwrap();
+    writer.write_bits(0x3F, 6).unwrap();
+    writer.finish().unwrap();
+    assert_eq!(buf, [0b1111_1011, 0b0000_0011]);
+}
+
+#[test]
+fn signed_fields_are_sign_extended() {
+    let mut reader = BitReader::new(&[0xF7, 0x80, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE][..]);
+    assert_eq!(reader.read_signed_bits(4).unwrap(), -1);
+    assert_eq!(reader.read_signed_bits(4).unwrap(), 7);
+    assert_eq!(reader.read_signed_bits(1).unwrap(), -1);
+    assert_eq!(reader.read_signed_bits(0).unwrap(), 0);
+    assert_eq!(reader.read_signed_bits(7).unwrap(), 0);
+    assert_eq!(reader.read_signed_bits(64).unwrap(), -2);
+
+    let mut reader = BitReader::with_order(&[0x0F][..], LsbFirst);
+    assert_eq!(reader.read_signed_bits(3).unwrap(), -1);
+    assert_eq!(reader.read_signed_bits(2).unwrap(), 1);
+
+    let mut buf = [0; 2];
+    let mut writer = BitWriter::new(&mut buf[..]);
+    writer.write_signed_bits(-5, 4).unwrap();
+    writer.write_signed_bits(7, 4).unwrap();
+    writer.write_signed_bits(-128, 8).unwrap();
+    writer.finish().unwrap();
+    assert_eq!(buf, [0xB7, 0x80]);
+}
+
+#[test]
+fn fields_which_do_not_fit_are_rejected() {
+    let mut buf = [0; 16];
+    let mut writer = BitWriter::new(&mut buf[..]);
+    writer.write_bits(0xFFF, 12).unwrap();
+
+    let err = writer.write_bits(8, 3).unwrap_err();
+    assert!(matches!(err, EndianError::Overflow { offset: 1, .. }), "{:?}", err);
+    let err = writer.write_signed_bits(8, 4).unwrap_err();
+    assert!(matches!(err, EndianError::Overflow { .. }), "{:?}", err);
+    let err = writer.write_signed_bits(-9, 4).unwrap_err();
+    assert!(matches!(err, EndianError::Overflow { .. }), "{:?}", err);
+    let err = writer.write_bits(1, 0).unwrap_err();
+    assert!(matches!(err, EndianError::Overflow { .. }), "{:?}", err);
+
+    // Rejected fields write nothing, full width fields accept every value
+    assert_eq!(writer.bit_position(), 12);
+    writer.write_bits(u64::MAX, 64).unwrap();
+    writer.write_signed_bits(i64::MIN, 64).unwrap();
+    assert_eq!(writer.bit_position(), 140);
+}
+
+#[test]
+fn align_to_byte_skips_the_rest_of_the_byte() {
+    let mut reader = BitReader::new(&[0b1010_1111, 0x42][..]);
+    assert!(reader.is_aligned());
+    assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+    assert!(!reader.is_aligned());
+    reader.align_to_byte();
+    assert!(reader.is_aligned());
+    assert_eq!(reader.bit_position(), 8);
+    assert_eq!(reader.read_bits(8).unwrap(), 0x42);
+
+    // Aligning an aligned reader does nothing
+    reader.align_to_byte();
+    assert_eq!(reader.bit_position(), 16);
+
+    let mut buf = [0xFF; 2];
+    let mut writer = BitWriter::with_order(&mut buf[..], LsbFirst);
+    writer.write_bits(0b101, 3).unwrap();
+    writer.align_to_byte();
+    assert!(writer.is_aligned());
+    assert_eq!(writer.bit_position(), 8);
+    writer.align_to_byte();
+    writer.write_bits(0x42, 8).unwrap();
+    writer.finish().unwrap();
+    assert_eq!(buf, [0b0000_0101, 0x42]);
+}
+
+#[test]
+fn flush_pads_with_zeros() {
+    let mut buf = [0xFF; 4];
+    let mut writer = BitWriter::new(&mut buf[..]);
+    writer.write_bit(true).unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.bit_position(), 8);
+
+    // Flushing again writes nothing
+    writer.flush().unwrap();
+    writer.write_bits(0b11, 2).unwrap();
+    let rest = writer.finish().unwrap();
+    assert_eq!(rest.len(), 2);
+    assert_eq!(buf, [0x80, 0xC0, 0xFF, 0xFF]);
+
+    let mut buf = [0xFF; 1];
+    let mut writer = BitWriter::with_order(&mut buf[..], LsbFirst);
+    writer.write_bit(true).unwrap();
+    writer.finish().unwrap();
+    assert_eq!(buf, [0x01]);
+}
+
+#[test]
+fn failed_reads_keep_the_buffered_bits() {
+    let mut reader = BitReader::new(&[0xAB, 0xCD][..]);
+    assert_eq!(reader.read_bits(4).unwrap(), 0xA);
+
+    let err = reader.read_bits(16).unwrap_err();
+    assert!(matches!(err, EndianError::EndOfStream(2)), "{:?}", err);
+    assert_eq!(reader.bit_position(), 4);
+
+    assert_eq!(reader.read_bits(12).unwrap(), 0xBCD);
+    assert!(matches!(reader.read_bit(), Err(EndianError::EndOfStream(2))));
+}
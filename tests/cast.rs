@@ -0,0 +1,93 @@
+use tinybit::{mut_from_bytes, ref_from_bytes, slice_from_bytes, slice_from_bytes_mut, U16Le, U32Be};
+
+/// A buffer aligned to 16 bytes, so `bytes[offset..]` is misaligned for `u32` at every odd `offset`
+#[repr(C, align(16))]
+struct Aligned {
+    bytes: [u8; 32]
+}
+
+impl Aligned {
+    fn new() -> Self {
+        let mut bytes = [0; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+
+        Aligned { bytes }
+    }
+}
+
+#[test]
+fn ref_from_bytes_checks_length_and_alignment() {
+    let buf = Aligned::new();
+    assert_eq!(ref_from_bytes::<u32>(&buf.bytes[..4]), Some(&u32::from_ne_bytes([0, 1, 2, 3])));
+    assert_eq!(ref_from_bytes::<u32>(&buf.bytes[4..8]), Some(&u32::from_ne_bytes([4, 5, 6, 7])));
+
+    assert_eq!(ref_from_bytes::<u32>(&buf.bytes[..3]), None);
+    assert_eq!(ref_from_bytes::<u32>(&buf.bytes[..8]), None);
+    assert_eq!(ref_from_bytes::<u32>(&buf.bytes[1..5]), None);
+    assert_eq!(ref_from_bytes::<u32>(&buf.bytes[1..]), None);
+
+    // Storage types are byte arrays, so any offset is aligned
+    assert_eq!(ref_from_bytes::<U32Be>(&buf.bytes[1..5]).unwrap().get(), 0x0102_0304);
+}
+
+#[test]
+fn slice_from_bytes_checks_length_and_alignment() {
+    let buf = Aligned::new();
+    let items = slice_from_bytes::<u32>(&buf.bytes[..8]).unwrap();
+    assert_eq!(items, &[u32::from_ne_bytes([0, 1, 2, 3]), u32::from_ne_bytes([4, 5, 6, 7])]);
+    assert_eq!(slice_from_bytes::<u32>(&buf.bytes[..0]).unwrap().len(), 0);
+
+    assert_eq!(slice_from_bytes::<u32>(&buf.bytes[..6]), None);
+    assert_eq!(slice_from_bytes::<u32>(&buf.bytes[1..9]), None);
+    assert_eq!(slice_from_bytes::<[u8; 0]>(&buf.bytes), None);
+
+    let items = slice_from_bytes::<U16Le>(&buf.bytes[1..5]).unwrap();
+    assert_eq!(items[0].get(), 0x0201);
+    assert_eq!(items[1].get(), 0x0403);
+}
+
+#[test]
+fn mutable_views_write_through() {
+    let mut buf = Aligned::new();
+    *mut_from_bytes::<u32>(&mut buf.bytes[4..8]).unwrap() = u32::from_ne_bytes([0xaa; 4]);
+    assert_eq!(&buf.bytes[4..8], &[0xaa; 4]);
+    assert!(mut_from_bytes::<u32>(&mut buf.bytes[5..9]).is_none());
+
+    for item in slice_from_bytes_mut::<U32Be>(&mut buf.bytes[9..17]).unwrap() {
+        item.set(0x0102_0304);
+    }
+    assert_eq!(&buf.bytes[9..17], &[1, 2, 3, 4, 1, 2, 3, 4]);
+    assert!(slice_from_bytes_mut::<u32>(&mut buf.bytes[1..]).is_none());
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derived_structs_read_their_fields() {
+    #[derive(tinybit::FromBytes, Clone, Copy, Debug)]
+    #[repr(C)]
+    struct Record {
+        magic: U32Be,
+        len: U16Le,
+        kind: U16Le
+    }
+
+    let bytes = [
+        0xca, 0xfe, 0xba, 0xbe, 0x10, 0x00, 0x02, 0x01,
+        0x00, 0x00, 0x00, 0x01, 0xff, 0xff, 0x00, 0x00
+    ];
+
+    // The fields are byte arrays, so records can be viewed at any offset
+    let record = ref_from_bytes::<Record>(&bytes[..8]).unwrap();
+    assert_eq!(record.magic.get(), 0xcafe_babe);
+    assert_eq!(record.len.get(), 0x10);
+    assert_eq!(record.kind.get(), 0x0102);
+    assert!(ref_from_bytes::<Record>(&bytes[..7]).is_none());
+
+    let records = slice_from_bytes::<Record>(&bytes).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].magic.get(), 1);
+    assert_eq!(records[1].len.get(), 0xffff);
+    assert_eq!(records[1].kind.get(), 0);
+}
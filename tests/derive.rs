@@ -0,0 +1,224 @@
+#![cfg(feature = "derive")]
+
+use tinybit::{Endian, EndianError, SliceReader, SliceWriter, Tagged, TryEndian};
+
+#[derive(Endian, Debug, PartialEq)]
+struct Header {
+    kind: u8,
+    len: u16,
+    id: u32
+}
+
+#[derive(Endian, Debug, PartialEq)]
+struct Mixed {
+    #[endian(big)]
+    magic: u16,
+    value: u16,
+    #[endian(little)]
+    flags: u16
+}
+
+#[derive(TryEndian, Debug, PartialEq)]
+#[repr(u8)]
+enum Message {
+    Ping,
+    Data(u32),
+    Pair { a: u8, b: u16 },
+    Close = 9
+}
+
+#[derive(Endian, Debug, PartialEq)]
+#[repr(u16)]
+enum Command {
+    Start = 1,
+    Stop(#[endian(big)] u16),
+    #[endian(unknown)]
+    Unknown(u16)
+}
+
+#[test]
+fn struct_fields_in_declaration_order() {
+    let header = Header { kind: 1, len: 0x0203, id: 0x0405_0607 };
+    assert_eq!(Header::SIZE, 7);
+
+    let mut le = [0; 7];
+    assert_eq!(header.write_le_into(&mut le).unwrap(), 7);
+    assert_eq!(le, [1, 0x03, 0x02, 0x07, 0x06, 0x05, 0x04]);
+
+    let mut be = [0; 7];
+    assert_eq!(header.write_be_into(&mut be).unwrap(), 7);
+    assert_eq!(be, [1, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07]);
+
+    assert_eq!(Header::read_le_from(&le).unwrap(), header);
+    assert_eq!(Header::read_be_from(&be).unwrap(), header);
+}
+
+#[test]
+fn field_order_overrides() {
+    let mixed = Mixed { magic: 0xCAFE, value: 0x0102, flags: 0x0304 };
+
+    let mut le = [0; 6];
+    mixed.write_le_into(&mut le).unwrap();
+    assert_eq!(le, [0xCA, 0xFE, 0x02, 0x01, 0x04, 0x03]);
+
+    let mut be = [0; 6];
+    mixed.write_be_into(&mut be).unwrap();
+    assert_eq!(be, [0xCA, 0xFE, 0x01, 0x02, 0x04, 0x03]);
+
+    assert_eq!(Mixed::read_le_from(&le).unwrap(), mixed);
+    assert_eq!(Mixed::read_be_from(&be).unwrap(), mixed);
+}
+
+#[test]
+fn variants_are_padded_to_the_largest_payload() {
+    assert_eq!(Message::SIZE, 5);
+
+    let cases = [
+        (Message::Ping, [0, 0, 0, 0, 0]),
+        (Message::Data(0x0102_0304), [1, 0x04, 0x03, 0x02, 0x01]),
+        (Message::Pair { a: 7, b: 0x0809 }, [2, 7, 0x09, 0x08, 0]),
+        (Message::Close, [9, 0, 0, 0, 0])
+    ];
+
+    for (message, bytes) in cases {
+        let mut out = [0xFF; 5];
+        assert_eq!(message.write_le_into(&mut out).unwrap(), 5);
+        assert_eq!(out, bytes);
+        assert_eq!(Message::try_read_le_from(&bytes).unwrap(), message);
+    }
+
+    // Padding is not checked when decoding
+    assert_eq!(Message::try_read_le_from(&[0, 1, 2, 3, 4]).unwrap(), Message::Ping);
+}
+
+#[test]
+fn unknown_tag_is_rejected() {
+    match Message::try_read_le_from(&[3, 0, 0, 0, 0]) {
+        Err(EndianError::InvalidValue { offset: 0, bytes, len: 1, .. }) => assert_eq!(bytes[0], 3),
+        other => panic!("unexpected result {:?}", other)
+    }
+}
+
+#[test]
+fn unknown_tag_is_kept() {
+    assert_eq!(Command::SIZE, 4);
+
+    let mut out = [0; 4];
+    Command::Stop(0x0102).write_le_into(&mut out).unwrap();
+    assert_eq!(out, [2, 0, 0x01, 0x02]);
+    assert_eq!(Command::read_le_from(&out).unwrap(), Command::Stop(0x0102));
+
+    let command = Command::read_be_from(&[0x12, 0x34, 0xAA, 0xBB]).unwrap();
+    assert_eq!(command, Command::Unknown(0x1234));
+
+    let mut out = [0xFF; 4];
+    command.write_be_into(&mut out).unwrap();
+    assert_eq!(out, [0x12, 0x34, 0, 0]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_round_trip() {
+    let mut buf = Vec::new();
+    Header { kind: 1, len: 2, id: 3 }.write_be(&mut buf).unwrap();
+    Message::Pair { a: 4, b: 5 }.write_be(&mut buf).unwrap();
+    Command::Unknown(6).write_be(&mut buf).unwrap();
+    assert_eq!(buf.len(), 7 + 5 + 4);
+
+    let mut src = &buf[..];
+    assert_eq!(Header::read_be(&mut src).unwrap(), Header { kind: 1, len: 2, id: 3 });
+    assert_eq!(Message::try_read_be(&mut src).unwrap(), Message::Pair { a: 4, b: 5 });
+    assert_eq!(Command::read_be(&mut src).unwrap(), Command::Unknown(6));
+    assert!(src.is_empty());
+}
+
+#[test]
+fn tagged_variants_are_not_padded() {
+    let cases: [(Message, &[u8]); 4] = [
+        (Message::Ping, &[0]),
+        (Message::Data(0x0102_0304), &[1, 0x01, 0x02, 0x03, 0x04]),
+        (Message::Pair { a: 7, b: 0x0809 }, &[2, 7, 0x08, 0x09]),
+        (Message::Close, &[9])
+    ];
+
+    let mut buf = [0; 16];
+    let mut writer = SliceWriter::new(&mut buf);
+    for (message, bytes) in &cases {
+        assert_eq!(message.tagged_len(), bytes.len());
+        assert_eq!(writer.write_tagged_be(message).unwrap(), bytes.len());
+    }
+    assert_eq!(writer.position(), 11);
+    assert_eq!(&buf[..11], &[0, 1, 0x01, 0x02, 0x03, 0x04, 2, 7, 0x08, 0x09, 9]);
+
+    // Each read stops at the end of its variant
+    let mut reader = SliceReader::new(&buf[..11]);
+    for (message, _) in &cases {
+        assert_eq!(&reader.read_tagged_be::<Message>().unwrap(), message);
+    }
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn tagged_reads_check_the_payload_and_tag() {
+    assert_eq!(Message::payload_len(&[2], tinybit::LittleEndian).unwrap(), 3);
+    assert_eq!(Command::payload_len(&[0x12, 0x34], tinybit::BigEndian).unwrap(), 0);
+
+    let mut reader = SliceReader::new(&[0, 1, 0x01, 0x02]);
+    assert_eq!(reader.read_tagged_le::<Message>().unwrap(), Message::Ping);
+    assert!(matches!(reader.read_tagged_le::<Message>(), Err(EndianError::EndOfStream(1))));
+    assert_eq!(reader.position(), 1);
+
+    match SliceReader::new(&[0xFF, 3]).read_tagged_le::<Message>().map(|_| ()) {
+        Err(EndianError::InvalidValue { offset: 0, bytes, len: 1, .. }) => assert_eq!(bytes[0], 0xFF),
+        other => panic!("unexpected result {:?}", other)
+    }
+
+    // Unknown tags are kept without a payload
+    let mut buf = [0; 4];
+    let mut writer = SliceWriter::new(&mut buf);
+    writer.write_tagged_le(&Command::Unknown(0x1234)).unwrap();
+    writer.write_tagged_le(&Command::Start).unwrap();
+    assert_eq!(buf, [0x34, 0x12, 1, 0]);
+
+    let mut reader = SliceReader::new(&buf);
+    assert_eq!(reader.read_tagged_le::<Command>().unwrap(), Command::Unknown(0x1234));
+    assert_eq!(reader.read_tagged_le::<Command>().unwrap(), Command::Start);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn tagged_vec_writer_matches_the_slice_writer() {
+    let mut writer = tinybit::VecWriter::with_order(tinybit::LittleEndian);
+    assert_eq!(writer.write_tagged(&Command::Stop(0x0102)), 4);
+    assert_eq!(writer.write_tagged_be(&Message::Ping), 1);
+    assert_eq!(writer.as_slice(), &[2, 0, 0x01, 0x02, 0]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn tagged_stream_leaves_the_next_message() {
+    use tinybit::{BigEndian, EndianReader, EndianWriter, ReadEndianExt, WriteEndianExt};
+
+    let mut buf = Vec::new();
+    let mut writer = EndianWriter::new(&mut buf, BigEndian);
+    writer.write_tagged(&Message::Ping).unwrap();
+    writer.write_tagged(&Message::Data(5)).unwrap();
+    assert_eq!(writer.position(), 6);
+    buf.write_tagged(BigEndian, &Command::Unknown(7)).unwrap();
+    buf.push(0xAA);
+    assert_eq!(buf, [0, 1, 0, 0, 0, 5, 0, 7, 0xAA]);
+
+    let mut src = &buf[..];
+    let mut reader = EndianReader::new(&mut src, BigEndian);
+    assert_eq!(reader.read_tagged::<Message>().unwrap(), Message::Ping);
+    assert_eq!(reader.read_tagged::<Message>().unwrap(), Message::Data(5));
+    assert_eq!(reader.position(), 6);
+    assert_eq!(src.read_tagged::<Command>(BigEndian).unwrap(), Command::Unknown(7));
+    assert_eq!(src, [0xAA]);
+
+    // A payload cut short is reported after the tag
+    match Message::read_tagged(&mut &[1u8, 0, 0][..], BigEndian) {
+        Err(EndianError::Io { offset: 1, .. }) => { }
+        other => panic!("unexpected result {:?}", other)
+    }
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "std")]
+
+use std::io::{self, Read, Write};
+
+use tinybit::{Endian, EndianError, EndianReader, EndianWriter, LittleEndian, ReadEndianExt, WriteEndianExt};
+
+/// A reader returning one byte per call, failing every other call with `Interrupted`
+struct Trickle<'a> {
+    bytes: &'a [u8],
+    interrupt: bool
+}
+
+impl<'a> Trickle<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Trickle { bytes, interrupt: true }
+    }
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if !self.interrupt {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+
+        match (self.bytes.split_first(), buf.first_mut()) {
+            (Some((&byte, rest)), Some(dst)) => {
+                *dst = byte;
+                self.bytes = rest;
+                Ok(1)
+            }
+            _ => Ok(0)
+        }
+    }
+}
+
+/// A writer accepting at most 3 bytes per call, interrupting every other call, until `limit` bytes
+/// were written after which it accepts nothing
+struct Partial {
+    bytes: Vec<u8>,
+    limit: usize,
+    interrupt: bool
+}
+
+impl Partial {
+    fn new(limit: usize) -> Self {
+        Partial { bytes: Vec::new(), limit, interrupt: true }
+    }
+}
+
+impl Write for Partial {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if !self.interrupt {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+
+        let count = buf.len().min(3).min(self.limit - self.bytes.len());
+        self.bytes.extend_from_slice(&buf[..count]);
+        Ok(count)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Unpacks an io error into its offset, expected and actual byte counts and kind
+fn io_error(err: EndianError) -> (usize, usize, usize, io::ErrorKind) {
+    match err {
+        EndianError::Io { offset, expected, actual, source, .. } => (offset, expected, actual, source.kind()),
+        other => panic!("expected an io error, got {:?}", other)
+    }
+}
+
+#[test]
+fn short_and_interrupted_reads_are_retried() {
+    let bytes = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0a, 0x0b, 0x0c, 0x0d, 0xee, 0xff];
+    let mut src = Trickle::new(&bytes);
+    assert_eq!(u64::read_le(&mut src).unwrap(), 0x0807_0605_0403_0201);
+    assert_eq!(src.read_be::<u32>().unwrap(), 0x0a0b_0c0d);
+
+    // Only the real end of the stream is reported, after the bytes which were there
+    let (offset, expected, actual, kind) = io_error(u32::read_le(&mut src).unwrap_err());
+    assert_eq!((offset, expected, actual, kind), (0, 4, 2, io::ErrorKind::UnexpectedEof));
+}
+
+#[test]
+fn short_reads_fill_slices_and_wrappers() {
+    let bytes = [0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x04];
+    let mut items = [0u16; 3];
+    u16::read_into_slice_le(&mut items, &mut Trickle::new(&bytes)).unwrap();
+    assert_eq!(items, [1, 2, 3]);
+
+    let mut reader = EndianReader::new(Trickle::new(&bytes), LittleEndian);
+    assert_eq!(reader.read::<[u16; 3]>().unwrap(), [1, 2, 3]);
+    let (offset, expected, actual, kind) = io_error(reader.read::<u16>().unwrap_err());
+    assert_eq!((offset, expected, actual, kind), (6, 2, 1, io::ErrorKind::UnexpectedEof));
+}
+
+#[test]
+fn partial_and_interrupted_writes_are_continued() {
+    let mut dst = Partial::new(usize::MAX);
+    assert_eq!(0x0102_0304_0506_0708u64.write_be(&mut dst).unwrap(), 8);
+    assert_eq!(dst.write_le(&0x0a0bu16).unwrap(), 2);
+    assert_eq!(dst.bytes, [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0b, 0x0a]);
+
+    let mut dst = Partial::new(usize::MAX);
+    assert_eq!(u16::write_slice_le(&[1, 2, 3], &mut dst).unwrap(), 6);
+    assert_eq!(dst.bytes, [1, 0, 2, 0, 3, 0]);
+}
+
+#[test]
+fn writes_accepting_nothing_are_reported() {
+    let mut dst = Partial::new(5);
+    let (offset, expected, actual, kind) = io_error(0x0102_0304_0506_0708u64.write_be(&mut dst).unwrap_err());
+    assert_eq!((offset, expected, actual, kind), (0, 8, 5, io::ErrorKind::WriteZero));
+    assert_eq!(dst.bytes, [0x01, 0x02, 0x03, 0x04, 0x05]);
+
+    let mut writer = EndianWriter::new(Partial::new(5), LittleEndian);
+    writer.write(&0x0102u16).unwrap();
+    let (offset, expected, actual, kind) = io_error(writer.write(&0x0304_0506u32).unwrap_err());
+    assert_eq!((offset, expected, actual, kind), (2, 4, 3, io::ErrorKind::WriteZero));
+}
@@ -0,0 +1,259 @@
+use core::ffi::CStr;
+
+use tinybit::{BigEndian, EndianError, LittleEndian, SliceReader, SliceWriter};
+
+/// Converts NUL terminated bytes into a `CStr`
+fn cstr(bytes: &[u8]) -> &CStr {
+    CStr::from_bytes_with_nul(bytes).unwrap()
+}
+
+/// Unpacks an `InvalidUtf8` error into its offset and the length of the valid prefix
+fn invalid_utf8(err: EndianError) -> (usize, usize) {
+    match err {
+        EndianError::InvalidUtf8 { offset, source } => (offset, source.valid_up_to()),
+        other => panic!("expected an invalid UTF-8 error, got {:?}", other)
+    }
+}
+
+#[test]
+fn prefixed_slices_are_borrowed() {
+    let bytes = [0xaa, 0x00, 0x03, b'a', b'b', b'c', 0x00, 0x02, b'h', b'i', 0xbb];
+    let mut reader = SliceReader::with_order(&bytes, BigEndian);
+    assert_eq!(reader.read_bytes(1).unwrap(), &[0xaa]);
+    assert_eq!(reader.read_bytes_prefixed::<u16>(3).unwrap(), b"abc");
+    assert_eq!(reader.read_str_prefixed::<u16>(2).unwrap(), "hi");
+    assert_eq!(reader.position(), 10);
+}
+
+#[test]
+fn prefixes_above_the_maximum_are_rejected() {
+    let bytes = [0xaa, 0x00, 0x04, b'a', b'b', b'c', b'd'];
+    let mut reader = SliceReader::with_order(&bytes, BigEndian);
+    reader.read_bytes(1).unwrap();
+
+    let err = reader.read_bytes_prefixed::<u16>(3).unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { offset: 1, len: 4, max: 3 }), "{:?}", err);
+    let err = reader.read_str_prefixed::<u16>(3).unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { offset: 1, len: 4, max: 3 }), "{:?}", err);
+
+    // Failed reads consume nothing, a prefix longer than the slice ends early
+    assert_eq!(reader.position(), 1);
+    let mut reader = SliceReader::with_order(&bytes[1..6], BigEndian);
+    assert!(matches!(reader.read_bytes_prefixed::<u16>(8), Err(EndianError::EndOfStream(_))));
+    assert_eq!(reader.position(), 0);
+}
+
+#[test]
+fn invalid_utf8_is_reported_at_the_string() {
+    let bytes = [0xaa, 0x04, b'o', b'k', 0xc3, 0x28];
+    let mut reader = SliceReader::with_order(&bytes, LittleEndian);
+    reader.read_bytes(1).unwrap();
+
+    let err = reader.read_str_prefixed::<u8>(8).unwrap_err();
+    assert_eq!(invalid_utf8(err), (2, 2));
+    assert_eq!(reader.position(), 1);
+
+    // The raw bytes are still readable
+    assert_eq!(reader.read_bytes_prefixed::<u8>(8).unwrap(), &[b'o', b'k', 0xc3, 0x28]);
+}
+
+#[test]
+fn slice_writers_check_the_prefix_type() {
+    let mut buf = [0; 8];
+    let mut writer = SliceWriter::with_order(&mut buf, BigEndian);
+    assert_eq!(writer.write_str_prefixed::<u16>("abc").unwrap(), 5);
+
+    let err = writer.write_bytes_prefixed::<u8>(&[0; 256]).unwrap_err();
+    assert!(matches!(err, EndianError::Overflow { offset: 5, type_name: "u8" }), "{:?}", err);
+
+    // A prefix and string which do not fit write nothing
+    assert!(matches!(writer.write_str_prefixed::<u8>("abc"), Err(EndianError::EndOfStream(_))));
+    assert_eq!(writer.position(), 5);
+    assert_eq!(&buf[..5], &[0x00, 0x03, b'a', b'b', b'c']);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_writers_use_the_same_layout() {
+    use tinybit::VecWriter;
+
+    let mut writer = VecWriter::with_order(LittleEndian);
+    assert_eq!(writer.write_bytes_prefixed::<u32>(&[1, 2]).unwrap(), 6);
+    assert_eq!(writer.write_str_prefixed::<u8>("").unwrap(), 1);
+    assert!(matches!(writer.write_bytes_prefixed::<u8>(&[0; 256]), Err(EndianError::Overflow { .. })));
+    assert_eq!(writer.as_slice(), &[0x02, 0x00, 0x00, 0x00, 1, 2, 0x00]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_prefixes_are_checked_before_allocating() {
+    use tinybit::{EndianReader, ReadEndianExt};
+
+    // Allocating the claimed length would abort the test
+    let mut src = &[0x3f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, b'a'][..];
+    let err = src.read_bytes_prefixed::<u64>(BigEndian, 16).unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { offset: 0, max: 16, .. }), "{:?}", err);
+    assert_eq!(src, b"a");
+
+    let mut reader = EndianReader::new(&[0x01, b'a', 0x10, b'b'][..], LittleEndian);
+    assert_eq!(reader.read_string_prefixed::<u8>(8).unwrap(), "a");
+    let err = reader.read_string_prefixed::<u8>(8).unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { offset: 2, len: 16, max: 8 }), "{:?}", err);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_strings_report_invalid_utf8_and_short_reads() {
+    use tinybit::{EndianReader, EndianWriter, ReadEndianExt, WriteEndianExt};
+
+    let mut buf = Vec::new();
+    assert_eq!(buf.write_str_prefixed::<u16>(BigEndian, "ok").unwrap(), 4);
+    assert_eq!(buf.write_bytes_prefixed::<u16>(BigEndian, &[b'x', 0xff]).unwrap(), 4);
+    assert!(matches!(buf.write_bytes_prefixed::<u8>(BigEndian, &[0; 256]), Err(EndianError::Overflow { .. })));
+
+    let mut reader = EndianReader::new(&buf[..], BigEndian);
+    assert_eq!(reader.read_string_prefixed::<u16>(8).unwrap(), "ok");
+    let err = reader.read_string_prefixed::<u16>(8).unwrap_err();
+    assert_eq!(invalid_utf8(err), (6, 1));
+
+    // The bytes after the prefix are missing
+    let mut src = &[0x00, 0x04, b'a', b'b'][..];
+    match src.read_bytes_prefixed::<u16>(BigEndian, 8).unwrap_err() {
+        EndianError::Io { offset, expected, actual, .. } => assert_eq!((offset, expected, actual), (2, 4, 2)),
+        other => panic!("expected an io error, got {:?}", other)
+    }
+
+    let mut writer = EndianWriter::new(Vec::new(), LittleEndian);
+    writer.write_str_prefixed::<u16>("a").unwrap();
+    assert_eq!(writer.write_str_prefixed::<u8>("bc").unwrap(), 3);
+    assert_eq!(writer.into_inner(), [0x01, 0x00, b'a', 0x02, b'b', b'c']);
+}
+
+#[test]
+fn nul_terminated_strings_stop_at_the_limit_or_the_end() {
+    let bytes = [0xaa, b'a', b'b', b'c', 0x00, b'd'];
+    let mut reader = SliceReader::with_order(&bytes, LittleEndian);
+    reader.read_bytes(1).unwrap();
+
+    // The limit excludes the terminator
+    let err = reader.read_cstr(2).unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { offset: 1, len: 3, max: 2 }), "{:?}", err);
+    let err = reader.read_bytes_nul(0).unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { offset: 1, len: 1, max: 0 }), "{:?}", err);
+    assert_eq!(reader.position(), 1);
+
+    assert_eq!(reader.read_cstr(3).unwrap().to_bytes(), b"abc");
+    assert_eq!(reader.position(), 5);
+
+    // Without a terminator the slice ends early, unless the limit is reached first
+    assert!(matches!(reader.read_bytes_nul(8), Err(EndianError::EndOfStream(5))));
+    assert!(matches!(reader.read_bytes_nul(1), Err(EndianError::EndOfStream(5))));
+    let err = reader.read_bytes_nul(0).unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { offset: 5, max: 0, .. }), "{:?}", err);
+    assert_eq!(reader.position(), 5);
+
+    let mut reader = SliceReader::with_order(&[0x00, 0x00], LittleEndian);
+    assert_eq!(reader.read_bytes_nul(0).unwrap(), b"");
+    assert_eq!(reader.read_cstr(0).unwrap().to_bytes(), b"");
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn padded_fields_strip_the_pad_byte() {
+    let bytes = *b"name    \0\0\0\0   x";
+    let mut reader = SliceReader::with_order(&bytes, LittleEndian);
+    assert_eq!(reader.read_str_padded(8, b' ').unwrap(), "name");
+    assert_eq!(reader.read_bytes_padded(4, 0).unwrap(), b"");
+    assert_eq!(reader.read_bytes_padded(4, 0).unwrap(), b"   x");
+    assert!(reader.is_empty());
+
+    // Only trailing pad bytes are stripped, including ones belonging to the value
+    let mut reader = SliceReader::with_order(b" a 00\0\0", LittleEndian);
+    assert_eq!(reader.read_bytes_padded(5, b'0').unwrap(), b" a ");
+    assert_eq!(reader.read_bytes_padded(2, b'0').unwrap(), b"\0\0");
+
+    let mut reader = SliceReader::with_order(&[0xaa, b'o', 0xff, 0x00], LittleEndian);
+    reader.read_bytes(1).unwrap();
+    assert_eq!(invalid_utf8(reader.read_str_padded(3, 0).unwrap_err()), (1, 1));
+    assert!(matches!(reader.read_bytes_padded(4, 0), Err(EndianError::EndOfStream(1))));
+    assert_eq!(reader.position(), 1);
+}
+
+#[test]
+fn slice_writers_pad_or_reject_fields() {
+    let mut buf = [0xaa; 16];
+    let mut writer = SliceWriter::with_order(&mut buf, LittleEndian);
+    assert_eq!(writer.write_str_padded("name", 6, b' ').unwrap(), 6);
+    assert_eq!(writer.write_bytes_padded(b"full", 4, 0).unwrap(), 4);
+
+    let err = writer.write_str_padded("toolong", 6, b' ').unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { offset: 10, len: 7, max: 6 }), "{:?}", err);
+    let err = writer.write_bytes_padded(b"x", 0, 0).unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { offset: 10, len: 1, max: 0 }), "{:?}", err);
+    assert!(matches!(writer.write_bytes_padded(b"x", 8, 0), Err(EndianError::EndOfStream(_))));
+    assert_eq!(writer.position(), 10);
+
+    assert_eq!(writer.write_cstr(cstr(b"ab\0")).unwrap(), 3);
+    assert!(matches!(writer.write_cstr(cstr(b"abc\0")), Err(EndianError::EndOfStream(_))));
+    assert_eq!(buf, *b"name  fullab\0\xaa\xaa\xaa");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_writers_pad_or_reject_fields() {
+    use tinybit::VecWriter;
+
+    let mut writer = VecWriter::with_order(LittleEndian);
+    assert_eq!(writer.write_cstr(cstr(b"id\0")), 3);
+    assert_eq!(writer.write_bytes_padded(b"ab", 4, 0).unwrap(), 4);
+
+    let err = writer.write_str_padded("abcde", 4, 0).unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { offset: 7, len: 5, max: 4 }), "{:?}", err);
+    assert_eq!(writer.as_slice(), b"id\0ab\0\0");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_nul_terminated_strings() {
+    use tinybit::ReadEndianExt;
+
+    // Bytes after the terminator are left in the stream
+    let mut src = &b"abc\0def"[..];
+    assert_eq!(src.read_cstring(3).unwrap().as_bytes(), b"abc");
+    assert_eq!(src, b"def");
+
+    let err = src.read_bytes_nul(2).unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { offset: 0, len: 3, max: 2 }), "{:?}", err);
+    assert_eq!(src, b"");
+
+    // A stream ending before the limit is an io error after the bytes which were read
+    let mut src = &b"def"[..];
+    match src.read_bytes_nul(8).unwrap_err() {
+        EndianError::Io { offset, source, .. } => {
+            assert_eq!(offset, 3);
+            assert_eq!(source.kind(), std::io::ErrorKind::UnexpectedEof);
+        },
+        other => panic!("expected an io error, got {:?}", other)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_padded_fields() {
+    use tinybit::{ReadEndianExt, WriteEndianExt};
+
+    let mut buf = Vec::new();
+    assert_eq!(buf.write_str_padded("name", 6, b' ').unwrap(), 6);
+    assert_eq!(buf.write_cstr(cstr(b"id\0")).unwrap(), 3);
+    let err = buf.write_bytes_padded(b"toolong", 4, 0).unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { offset: 0, len: 7, max: 4 }), "{:?}", err);
+    assert_eq!(buf, b"name  id\0");
+
+    let mut src = &buf[..];
+    assert_eq!(src.read_string_padded(6, b' ').unwrap(), "name");
+    assert_eq!(src.read_bytes_padded(3, 0).unwrap(), b"id");
+    assert!(src.is_empty());
+
+    let mut src = &[b'o', 0xff, 0x00][..];
+    assert_eq!(invalid_utf8(src.read_string_padded(3, 0).unwrap_err()), (0, 1));
+}
@@ -0,0 +1,154 @@
+#![cfg(feature = "alloc")]
+
+use tinybit::{BigEndian, EndianError, LittleEndian, SliceReader, TextEncoding, VecWriter};
+
+/// Unpacks an `InvalidUnicode` error into its offset and offending code unit
+fn invalid(err: EndianError) -> (usize, u32) {
+    match err {
+        EndianError::InvalidUnicode { offset, unit, .. } => (offset, unit),
+        other => panic!("expected an invalid unicode error, got {:?}", other)
+    }
+}
+
+#[test]
+fn byte_order_marks_override_the_requested_order() {
+    let utf16_be = [0xfe, 0xff, 0x00, 0x41, 0x00, 0x00];
+    let mut reader = SliceReader::with_order(&utf16_be, LittleEndian);
+    assert_eq!(reader.read_text_nul(TextEncoding::Utf16, 8).unwrap(), "A");
+    assert!(reader.is_empty());
+
+    let utf16_le = [0xff, 0xfe, 0x41, 0x00, 0x00, 0x00];
+    let mut reader = SliceReader::with_order(&utf16_le, BigEndian);
+    assert_eq!(reader.read_text_nul(TextEncoding::Utf16, 8).unwrap(), "A");
+
+    let utf32_be = [0x00, 0x00, 0xfe, 0xff, 0x00, 0x00, 0x00, 0x41];
+    let mut reader = SliceReader::with_order(&utf32_be, LittleEndian);
+    assert_eq!(reader.read_text_padded(TextEncoding::Utf32, 2).unwrap(), "A");
+
+    let utf32_le = [0x02, 0xff, 0xfe, 0x00, 0x00, 0x41, 0x00, 0x00, 0x00];
+    let mut reader = SliceReader::with_order(&utf32_le, BigEndian);
+    assert_eq!(reader.read_text_prefixed::<u8>(TextEncoding::Utf32, 2).unwrap(), "A");
+
+    // Without a mark the requested order is used
+    let mut reader = SliceReader::with_order(&[0x00, 0x41, 0x00, 0x00], BigEndian);
+    assert_eq!(reader.read_text_nul(TextEncoding::Utf16, 8).unwrap(), "A");
+}
+
+#[test]
+fn unpaired_surrogates_are_rejected_at_their_offset() {
+    // A high surrogate followed by a regular character, after a one byte prefix
+    let bytes = [0x03, 0x41, 0x00, 0x00, 0xd8, 0x42, 0x00];
+    let mut reader = SliceReader::with_order(&bytes, LittleEndian);
+    let err = reader.read_text_prefixed::<u8>(TextEncoding::Utf16, 8).unwrap_err();
+    assert_eq!(invalid(err), (3, 0xd800));
+    assert_eq!(reader.position(), 0);
+
+    // A high surrogate ending the text
+    let bytes = [0x00, 0x41, 0xdb, 0xff, 0x00, 0x00];
+    let err = SliceReader::with_order(&bytes, BigEndian).read_text_nul(TextEncoding::Utf16, 8).unwrap_err();
+    assert_eq!(invalid(err), (2, 0xdbff));
+
+    // A low surrogate without a high one, the offset includes the byte order mark
+    let bytes = [0xff, 0xfe, 0x00, 0xdc, 0x00, 0x00];
+    let err = SliceReader::with_order(&bytes, BigEndian).read_text_nul(TextEncoding::Utf16, 8).unwrap_err();
+    assert_eq!(invalid(err), (2, 0xdc00));
+
+    // Neither surrogates nor values above U+10FFFF are characters in UTF-32
+    let bytes = [0x41, 0x00, 0x00, 0x00, 0x00, 0xd8, 0x00, 0x00];
+    let err = SliceReader::with_order(&bytes, LittleEndian).read_text_padded(TextEncoding::Utf32, 2).unwrap_err();
+    assert_eq!(invalid(err), (4, 0xd800));
+
+    let bytes = [0x00, 0x11, 0x00, 0x00];
+    let err = SliceReader::with_order(&bytes, BigEndian).read_text_padded(TextEncoding::Utf32, 1).unwrap_err();
+    assert_eq!(invalid(err), (0, 0x0011_0000));
+}
+
+#[test]
+fn characters_outside_the_bmp_round_trip() {
+    let text = "a\u{1f600}b\u{10ffff}";
+    assert_eq!(TextEncoding::Utf16.encoded_len(text), 6);
+    assert_eq!(TextEncoding::Utf32.encoded_len(text), 4);
+
+    let mut writer = VecWriter::with_order(BigEndian);
+    assert_eq!(writer.write_text_prefixed::<u16>(TextEncoding::Utf16, text).unwrap(), 14);
+    assert_eq!(writer.as_slice(), &[
+        0x00, 0x06,
+        0x00, 0x61, 0xd8, 0x3d, 0xde, 0x00, 0x00, 0x62, 0xdb, 0xff, 0xdf, 0xff
+    ]);
+    assert_eq!(writer.write_text_prefixed::<u16>(TextEncoding::Utf32, text).unwrap(), 18);
+
+    let mut reader = SliceReader::with_order(writer.as_slice(), BigEndian);
+    assert_eq!(reader.read_text_prefixed::<u16>(TextEncoding::Utf16, 6).unwrap(), text);
+    assert_eq!(reader.read_text_prefixed::<u16>(TextEncoding::Utf32, 4).unwrap(), text);
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn slice_and_vec_layouts() {
+    let mut writer = VecWriter::with_order(LittleEndian);
+    assert_eq!(writer.write_text_nul(TextEncoding::Utf16, "hi"), 6);
+    assert_eq!(writer.write_text_padded(TextEncoding::Utf16, "hi", 4).unwrap(), 8);
+    assert_eq!(writer.write_text_padded(TextEncoding::Utf32, "\u{e9}", 2).unwrap(), 8);
+    assert_eq!(writer.as_slice(), &[
+        0x68, 0x00, 0x69, 0x00, 0x00, 0x00,
+        0x68, 0x00, 0x69, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0xe9, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+    ]);
+
+    // Text wider than the field is rejected without writing anything
+    let err = writer.write_text_padded(TextEncoding::Utf16, "\u{1f600}", 1).unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { len: 4, max: 2, .. }), "{:?}", err);
+    assert_eq!(writer.position(), 22);
+
+    let mut reader = SliceReader::with_order(writer.as_slice(), LittleEndian);
+    assert_eq!(reader.read_text_nul(TextEncoding::Utf16, 2).unwrap(), "hi");
+    assert_eq!(reader.read_text_padded(TextEncoding::Utf16, 4).unwrap(), "hi");
+    assert_eq!(reader.read_text_padded(TextEncoding::Utf32, 2).unwrap(), "\u{e9}");
+    assert!(reader.is_empty());
+
+    // Limits count code units
+    let mut reader = SliceReader::with_order(writer.as_slice(), LittleEndian);
+    let err = reader.read_text_nul(TextEncoding::Utf16, 1).unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { offset: 0, max: 2, .. }), "{:?}", err);
+
+    // Text without a terminator ends early
+    let mut reader = SliceReader::with_order(&[0x41, 0x00, 0x42], LittleEndian);
+    assert!(matches!(reader.read_text_nul(TextEncoding::Utf16, 8), Err(EndianError::EndOfStream(0))));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_layouts() {
+    use tinybit::{ReadEndianExt, WriteEndianExt};
+
+    let mut buf = Vec::new();
+    assert_eq!(buf.write_text_prefixed::<u8>(TextEncoding::Utf32, BigEndian, "\u{1f600}").unwrap(), 5);
+    assert_eq!(buf.write_text_nul(TextEncoding::Utf16, LittleEndian, "\u{1f600}").unwrap(), 6);
+    assert_eq!(buf.write_text_padded(TextEncoding::Utf16, BigEndian, "a", 3).unwrap(), 6);
+    buf.push(0xaa);
+    assert_eq!(buf, [
+        0x01, 0x00, 0x01, 0xf6, 0x00,
+        0x3d, 0xd8, 0x00, 0xde, 0x00, 0x00,
+        0x00, 0x61, 0x00, 0x00, 0x00, 0x00,
+        0xaa
+    ]);
+
+    let mut src = &buf[..];
+    assert_eq!(src.read_text_prefixed::<u8>(TextEncoding::Utf32, BigEndian, 1).unwrap(), "\u{1f600}");
+    assert_eq!(src.read_text_nul(TextEncoding::Utf16, LittleEndian, 2).unwrap(), "\u{1f600}");
+    assert_eq!(src.read_text_padded(TextEncoding::Utf16, BigEndian, 3).unwrap(), "a");
+    assert_eq!(src, [0xaa]);
+
+    // The prefix is checked before the text is read
+    let mut src = &buf[..];
+    let err = src.read_text_prefixed::<u8>(TextEncoding::Utf32, BigEndian, 0).unwrap_err();
+    assert!(matches!(err, EndianError::TooLong { len: 4, max: 0, .. }), "{:?}", err);
+
+    // Byte order marks apply to streams as well
+    let mut src = &[0xfe, 0xff, 0xd8, 0x3d, 0xde, 0x00, 0x00, 0x00][..];
+    assert_eq!(src.read_text_nul(TextEncoding::Utf16, LittleEndian, 8).unwrap(), "\u{1f600}");
+
+    let mut src = &[0x00, 0xdc, 0x00, 0x00][..];
+    let err = src.read_text_nul(TextEncoding::Utf16, LittleEndian, 8).unwrap_err();
+    assert_eq!(invalid(err), (0, 0xdc00));
+}
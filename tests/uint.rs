@@ -0,0 +1,109 @@
+use tinybit::{EndianError, SliceReader, SliceWriter};
+
+/// Writes `value` as a signed integer of `nbytes` bytes in both byte orders and reads it back
+fn int_round_trip(value: i64, nbytes: usize, le: &[u8]) {
+    let mut buf = [0; 8];
+    assert_eq!(SliceWriter::new(&mut buf).write_int_le(value, nbytes).unwrap(), nbytes);
+    assert_eq!(&buf[..nbytes], le, "little endian bytes of {}", value);
+    assert_eq!(SliceReader::new(&buf).read_int_le(nbytes).unwrap(), value);
+
+    let be: Vec<u8> = le.iter().rev().copied().collect();
+    assert_eq!(SliceWriter::new(&mut buf).write_int_be(value, nbytes).unwrap(), nbytes);
+    assert_eq!(&buf[..nbytes], &be[..], "big endian bytes of {}", value);
+    assert_eq!(SliceReader::new(&buf).read_int_be(nbytes).unwrap(), value);
+}
+
+#[test]
+fn signed_values_are_sign_extended() {
+    assert_eq!(SliceReader::new(&[0xff, 0xff, 0x80]).read_int_le(3).unwrap(), -8_323_073);
+    assert_eq!(SliceReader::new(&[0x80, 0xff, 0xff]).read_int_be(3).unwrap(), -8_323_073);
+    assert_eq!(SliceReader::new(&[0xff, 0xff, 0x7f]).read_int_le(3).unwrap(), 0x7f_ffff);
+    assert_eq!(SliceReader::new(&[0, 0, 0, 0, 0, 0x80]).read_int_le(6).unwrap(), -0x8000_0000_0000);
+    assert_eq!(SliceReader::new(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xfe]).read_int_be(6).unwrap(), -2);
+
+    int_round_trip(-1, 3, &[0xff, 0xff, 0xff]);
+    int_round_trip(-0x80_0000, 3, &[0x00, 0x00, 0x80]);
+    int_round_trip(0x7f_ffff, 3, &[0xff, 0xff, 0x7f]);
+    int_round_trip(-0x8000_0000_0000, 6, &[0, 0, 0, 0, 0, 0x80]);
+    int_round_trip(0x7fff_ffff_ffff, 6, &[0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]);
+}
+
+#[test]
+fn unsigned_values_are_zero_extended() {
+    assert_eq!(SliceReader::new(&[0xff, 0xff, 0x80]).read_uint_le(3).unwrap(), 0x80_ffff);
+    assert_eq!(SliceReader::new(&[0x80, 0xff, 0xff]).read_uint_be(3).unwrap(), 0x80_ffff);
+
+    let mut buf = [0; 6];
+    SliceWriter::new(&mut buf).write_uint_be(0x0102_0304_0506, 6).unwrap();
+    assert_eq!(buf, [1, 2, 3, 4, 5, 6]);
+    assert_eq!(SliceReader::new(&buf).read_uint_le(6).unwrap(), 0x0605_0403_0201);
+}
+
+#[test]
+fn values_outside_the_width_are_rejected() {
+    let mut buf = [0; 8];
+    let mut writer = SliceWriter::new(&mut buf);
+    writer.write_uint_le(1, 1).unwrap();
+
+    writer.write_int_le(0x7f_ffff, 3).unwrap();
+    let err = writer.write_int_le(0x80_0000, 3).unwrap_err();
+    assert!(matches!(err, EndianError::Overflow { offset: 4, type_name: "i24" }), "{:?}", err);
+
+    let mut writer = SliceWriter::new(&mut buf);
+    writer.write_int_be(-0x80_0000, 3).unwrap();
+    let err = writer.write_int_be(-0x80_0001, 3).unwrap_err();
+    assert!(matches!(err, EndianError::Overflow { offset: 3, type_name: "i24" }), "{:?}", err);
+
+    let mut writer = SliceWriter::new(&mut buf);
+    writer.write_uint_be(0xff_ffff, 3).unwrap();
+    let err = writer.write_uint_be(0x100_0000, 3).unwrap_err();
+    assert!(matches!(err, EndianError::Overflow { offset: 3, type_name: "u24" }), "{:?}", err);
+
+    // Rejected values write nothing
+    assert_eq!(writer.position(), 3);
+}
+
+#[test]
+fn eight_bytes_hold_every_value() {
+    int_round_trip(i64::MIN, 8, &[0, 0, 0, 0, 0, 0, 0, 0x80]);
+    int_round_trip(i64::MAX, 8, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]);
+
+    let mut buf = [0; 8];
+    SliceWriter::new(&mut buf).write_uint_le(u64::MAX, 8).unwrap();
+    assert_eq!(buf, [0xff; 8]);
+    assert_eq!(SliceReader::new(&buf).read_uint_be(8).unwrap(), u64::MAX);
+    assert_eq!(SliceReader::new(&buf).read_int_be(8).unwrap(), -1);
+}
+
+#[test]
+fn short_slices_are_reported() {
+    let mut reader = SliceReader::new(&[1, 2]);
+    assert!(matches!(reader.read_uint_le(3), Err(EndianError::EndOfStream(0))));
+    assert_eq!(reader.position(), 0);
+
+    let mut buf = [0; 2];
+    assert!(matches!(SliceWriter::new(&mut buf).write_int_le(1, 3), Err(EndianError::EndOfStream(0))));
+}
+
+#[test]
+#[should_panic(expected = "integer width must be between 1 and 8 bytes")]
+fn widths_above_eight_bytes_panic() {
+    let _ = SliceReader::new(&[0; 16]).read_uint_le(9);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn streams_use_the_same_encoding() {
+    use tinybit::{ReadEndianExt, WriteEndianExt};
+
+    let mut buf = Vec::new();
+    assert_eq!(buf.write_int_le(-8_323_073, 3).unwrap(), 3);
+    assert_eq!(buf.write_uint_be(0x0102_0304_0506, 6).unwrap(), 6);
+    assert!(matches!(buf.write_int_be(-0x80_0001, 3), Err(EndianError::Overflow { .. })));
+    assert_eq!(buf, [0xff, 0xff, 0x80, 1, 2, 3, 4, 5, 6]);
+
+    let mut src = &buf[..];
+    assert_eq!(src.read_int_le(3).unwrap(), -8_323_073);
+    assert_eq!(src.read_int_be(6).unwrap(), 0x0102_0304_0506);
+    assert!(src.is_empty());
+}
@@ -0,0 +1,115 @@
+use tinybit::{Endian, SliceReader, SliceWriter};
+
+/// A buffer aligned to 16 bytes, so `bytes[offset..]` is misaligned for every odd `offset`
+#[repr(C, align(16))]
+struct Aligned {
+    bytes: [u8; 64]
+}
+
+impl Aligned {
+    fn new() -> Self {
+        Aligned { bytes: [0; 64] }
+    }
+}
+
+/// Writes `value` at every offset from 1 to 15 of an aligned buffer and reads it back
+fn round_trip<T>(value: T, le: &[u8], be: &[u8])
+    where T: Endian + PartialEq + core::fmt::Debug
+{
+    assert_eq!(le.len(), T::SIZE);
+    assert_eq!(be.len(), T::SIZE);
+
+    for offset in 1..16 {
+        let mut buf = Aligned::new();
+        let out = &mut buf.bytes[offset..];
+
+        assert_eq!(value.write_le_into(out).unwrap(), T::SIZE);
+        assert_eq!(&out[..T::SIZE], le, "little endian bytes at offset {}", offset);
+        assert_eq!(T::read_le_from(out).unwrap(), value, "little endian value at offset {}", offset);
+
+        assert_eq!(value.write_be_into(out).unwrap(), T::SIZE);
+        assert_eq!(&out[..T::SIZE], be, "big endian bytes at offset {}", offset);
+        assert_eq!(T::read_be_from(out).unwrap(), value, "big endian value at offset {}", offset);
+    }
+}
+
+#[test]
+fn scalars_at_odd_offsets() {
+    round_trip(0x0102u16, &[0x02, 0x01], &[0x01, 0x02]);
+    round_trip(-2i16, &[0xfe, 0xff], &[0xff, 0xfe]);
+    round_trip(0x0102_0304u32, &[0x04, 0x03, 0x02, 0x01], &[0x01, 0x02, 0x03, 0x04]);
+    round_trip(
+        0x0102_0304_0506_0708u64,
+        &[0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01],
+        &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]
+    );
+    round_trip(
+        0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10u128,
+        &[0x10, 0x0f, 0x0e, 0x0d, 0x0c, 0x0b, 0x0a, 0x09, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01],
+        &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10]
+    );
+    round_trip(1.5f32, &[0x00, 0x00, 0xc0, 0x3f], &[0x3f, 0xc0, 0x00, 0x00]);
+    round_trip(
+        -2.5f64,
+        &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0xc0],
+        &[0xc0, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+    );
+}
+
+#[test]
+fn compound_values_at_odd_offsets() {
+    round_trip(
+        [0x0102u16, 0x0304, 0x0506],
+        &[0x02, 0x01, 0x04, 0x03, 0x06, 0x05],
+        &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06]
+    );
+    round_trip(
+        (0x01u8, 0x0203u16, 0x0405_0607u32),
+        &[0x01, 0x03, 0x02, 0x07, 0x06, 0x05, 0x04],
+        &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07]
+    );
+}
+
+#[test]
+fn slice_reader_at_odd_offsets() {
+    let mut buf = Aligned::new();
+    buf.bytes[1..16].copy_from_slice(&[
+        0x01,
+        0x03, 0x02,
+        0x07, 0x06, 0x05, 0x04,
+        0x0f, 0x0e, 0x0d, 0x0c, 0x0b, 0x0a, 0x09, 0x08
+    ]);
+
+    // Every value after the first starts at an odd address
+    let mut reader = SliceReader::new(&buf.bytes[1..16]);
+    assert_eq!(reader.read_le::<u8>().unwrap(), 0x01);
+    assert_eq!(reader.read_le::<u16>().unwrap(), 0x0203);
+    assert_eq!(reader.read_le::<u32>().unwrap(), 0x0405_0607);
+    assert_eq!(reader.read_le::<u64>().unwrap(), 0x0809_0a0b_0c0d_0e0f);
+    assert!(reader.is_empty());
+
+    let mut reader = SliceReader::new(&buf.bytes[2..16]);
+    assert_eq!(reader.read_be::<u16>().unwrap(), 0x0302);
+    assert_eq!(reader.read_be::<[u32; 3]>().unwrap(), [0x0706_0504, 0x0f0e_0d0c, 0x0b0a_0908]);
+}
+
+#[test]
+fn slice_writer_at_odd_offsets() {
+    let mut buf = Aligned::new();
+    let mut writer = SliceWriter::new(&mut buf.bytes[1..]);
+    writer.write_le(&0x01u8).unwrap();
+    writer.write_le(&0x0203u16).unwrap();
+    writer.write_be(&0x0405_0607u32).unwrap();
+    writer.write_le(&0x0809_0a0b_0c0d_0e0fu64).unwrap();
+    writer.write_be(&0x1011_1213_1415_1617_1819_1a1b_1c1d_1e1fu128).unwrap();
+    assert_eq!(writer.position(), 31);
+
+    assert_eq!(&buf.bytes[..32], &[
+        0x00,
+        0x01,
+        0x03, 0x02,
+        0x04, 0x05, 0x06, 0x07,
+        0x0f, 0x0e, 0x0d, 0x0c, 0x0b, 0x0a, 0x09, 0x08,
+        0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f
+    ]);
+}
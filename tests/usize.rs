@@ -0,0 +1,76 @@
+use tinybit::{BigEndian, EndianError, LittleEndian, SliceReader, SliceWriter};
+
+/// Checks that `err` is an overflow of `type_name` at `offset`
+fn assert_overflow(err: EndianError, offset: usize, type_name: &str) {
+    match err {
+        EndianError::Overflow { offset: at, type_name: name } => assert_eq!((at, name), (offset, type_name)),
+        other => panic!("expected an overflow error, got {:?}", other)
+    }
+}
+
+#[test]
+fn cursors_store_sizes_as_fixed_width_integers() {
+    let mut buf = [0; 16];
+    let mut writer = SliceWriter::with_order(&mut buf, BigEndian);
+    assert_eq!(writer.write_usize_as::<u16>(0x1234).unwrap(), 2);
+    assert_eq!(writer.write_isize_as::<i32>(-2).unwrap(), 4);
+    assert_eq!(&buf[..6], &[0x12, 0x34, 0xff, 0xff, 0xff, 0xfe]);
+
+    let mut reader = SliceReader::with_order(&buf, BigEndian);
+    assert_eq!(reader.read_usize_as::<u16>().unwrap(), 0x1234);
+    assert_eq!(reader.read_isize_as::<i32>().unwrap(), -2);
+}
+
+#[test]
+fn cursors_reject_sizes_which_do_not_fit() {
+    let mut buf = [0; 16];
+    let mut writer = SliceWriter::with_order(&mut buf, LittleEndian);
+    writer.write_usize_as::<u16>(0xffff).unwrap();
+    assert_overflow(writer.write_usize_as::<u16>(70000).unwrap_err(), 2, "u16");
+    assert_overflow(writer.write_isize_as::<i8>(128).unwrap_err(), 2, "i8");
+    assert_overflow(writer.write_isize_as::<u32>(-1).unwrap_err(), 2, "u32");
+    assert_eq!(writer.position(), 2);
+
+    // Negative and oversized stored values do not fit in a usize on any target
+    let bytes = [0x00, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0];
+    let mut reader = SliceReader::with_order(&bytes, LittleEndian);
+    reader.skip(1).unwrap();
+    assert_overflow(reader.read_usize_as::<i32>().unwrap_err(), 1, "usize");
+    assert_eq!(reader.position(), 1);
+    assert_eq!(reader.read_isize_as::<i32>().unwrap(), -1);
+    assert_overflow(reader.read_usize_as::<u128>().unwrap_err(), 5, "usize");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_writer_rejects_sizes_which_do_not_fit() {
+    let mut writer = tinybit::VecWriter::with_order(BigEndian);
+    writer.write_usize_as::<u32>(70000).unwrap();
+    assert_overflow(writer.write_usize_as::<u16>(70000).unwrap_err(), 4, "u16");
+    assert_eq!(writer.as_slice(), &[0x00, 0x01, 0x11, 0x70]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn streams_reject_sizes_which_do_not_fit() {
+    use tinybit::{EndianReader, EndianWriter, ReadEndianExt, WriteEndianExt};
+
+    let mut buf = Vec::new();
+    assert_eq!(buf.write_usize_as::<u16>(BigEndian, 0x1234).unwrap(), 2);
+    assert_overflow(buf.write_usize_as::<u16>(BigEndian, 70000).unwrap_err(), 0, "u16");
+    assert_overflow(buf.write_isize_as::<i16>(LittleEndian, -40000).unwrap_err(), 0, "i16");
+    assert_eq!(buf, [0x12, 0x34]);
+
+    let mut writer = EndianWriter::new(&mut buf, LittleEndian);
+    writer.write_isize_as::<i16>(-2).unwrap();
+    assert_overflow(writer.write_usize_as::<u16>(70000).unwrap_err(), 2, "u16");
+    assert_eq!(buf, [0x12, 0x34, 0xfe, 0xff]);
+
+    let mut src = &buf[..];
+    assert_eq!(src.read_usize_as::<u16>(BigEndian).unwrap(), 0x1234);
+    assert_overflow(src.read_usize_as::<i16>(LittleEndian).unwrap_err(), 0, "usize");
+
+    let mut reader = EndianReader::new(&[0x12, 0x34, 0xfe, 0xff][..], LittleEndian);
+    assert_eq!(reader.read_usize_as::<u16>().unwrap(), 0x3412);
+    assert_overflow(reader.read_usize_as::<i16>().unwrap_err(), 2, "usize");
+}
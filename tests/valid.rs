@@ -0,0 +1,125 @@
+use core::num::{NonZeroI16, NonZeroU32, NonZeroU8};
+
+use tinybit::{Endian, EndianError, SliceReader, SliceWriter, TryEndian};
+
+/// Unpacks an `InvalidValue` error into its offset, type name and offending bytes
+fn invalid(err: EndianError) -> (usize, &'static str, Vec<u8>) {
+    match err {
+        EndianError::InvalidValue { offset, type_name, bytes, len } => (offset, type_name, bytes[..len].to_vec()),
+        other => panic!("expected an invalid value error, got {:?}", other)
+    }
+}
+
+#[test]
+fn bool_accepts_only_zero_and_one() {
+    assert!(!bool::try_read_le_from(&[0]).unwrap());
+    assert!(bool::try_read_le_from(&[1]).unwrap());
+    assert_eq!(invalid(bool::try_read_le_from(&[2]).unwrap_err()), (0, "bool", vec![2]));
+    assert_eq!(invalid(bool::try_read_be_from(&[0xff]).unwrap_err()), (0, "bool", vec![0xff]));
+}
+
+#[test]
+fn char_rejects_surrogates_and_values_above_the_unicode_range() {
+    assert_eq!(char::try_read_le_from(&[0x00, 0xf6, 0x01, 0x00]).unwrap(), '\u{1f600}');
+    assert_eq!(char::try_read_be_from(&[0x00, 0x10, 0xff, 0xff]).unwrap(), '\u{10ffff}');
+
+    // The offending bytes are reported in the order they were read
+    let le = invalid(char::try_read_le_from(&[0x00, 0xd8, 0x00, 0x00]).unwrap_err());
+    assert_eq!(le, (0, "char", vec![0x00, 0xd8, 0x00, 0x00]));
+    let be = invalid(char::try_read_be_from(&[0x00, 0x00, 0xd8, 0x00]).unwrap_err());
+    assert_eq!(be, (0, "char", vec![0x00, 0x00, 0xd8, 0x00]));
+
+    let le = invalid(char::try_read_le_from(&[0x00, 0x00, 0x11, 0x00]).unwrap_err());
+    assert_eq!(le, (0, "char", vec![0x00, 0x00, 0x11, 0x00]));
+    let be = invalid(char::try_read_be_from(&[0xff, 0xff, 0xff, 0xff]).unwrap_err());
+    assert_eq!(be, (0, "char", vec![0xff, 0xff, 0xff, 0xff]));
+}
+
+#[test]
+fn non_zero_rejects_zero() {
+    assert_eq!(NonZeroU8::try_read_le_from(&[7]).unwrap().get(), 7);
+    assert_eq!(NonZeroU32::try_read_be_from(&[0, 0, 1, 0]).unwrap().get(), 0x100);
+    assert_eq!(NonZeroI16::try_read_le_from(&[0xff, 0xff]).unwrap().get(), -1);
+
+    assert_eq!(invalid(NonZeroU8::try_read_le_from(&[0]).unwrap_err()), (0, "NonZeroU8", vec![0]));
+    assert_eq!(invalid(NonZeroU32::try_read_be_from(&[0; 4]).unwrap_err()), (0, "NonZeroU32", vec![0; 4]));
+    assert_eq!(invalid(NonZeroI16::try_read_le_from(&[0; 2]).unwrap_err()), (0, "NonZeroI16", vec![0; 2]));
+}
+
+#[test]
+fn optional_non_zero_maps_zero_to_none() {
+    assert_eq!(Option::<NonZeroU32>::read_le_from(&[0; 4]).unwrap(), None);
+    assert_eq!(Option::<NonZeroU32>::read_be_from(&[0, 0, 0, 5]).unwrap(), NonZeroU32::new(5));
+
+    let mut out = [0xff; 4];
+    None::<NonZeroU32>.write_le_into(&mut out).unwrap();
+    assert_eq!(out, [0; 4]);
+    NonZeroU32::new(5).write_be_into(&mut out).unwrap();
+    assert_eq!(out, [0, 0, 0, 5]);
+}
+
+#[test]
+fn cursors_report_the_offset_and_keep_their_position() {
+    let mut reader = SliceReader::new(&[1, 0x00, 0x00, 0x11, 0x00]);
+    assert!(reader.try_read_le::<bool>().unwrap());
+
+    let (offset, _, bytes) = invalid(reader.try_read_le::<char>().unwrap_err());
+    assert_eq!((offset, bytes), (1, vec![0x00, 0x00, 0x11, 0x00]));
+    assert_eq!(reader.position(), 1);
+}
+
+#[test]
+fn writers_accept_validated_values() {
+    let mut buf = [0; 12];
+    let mut writer = SliceWriter::new(&mut buf);
+    assert_eq!(writer.try_write_le(&true).unwrap(), 1);
+    assert_eq!(writer.try_write_be(&'\u{1f600}').unwrap(), 4);
+    assert_eq!(writer.try_write_le(&NonZeroI16::new(-2).unwrap()).unwrap(), 2);
+    assert_eq!(writer.try_write_be(&false).unwrap(), 1);
+    assert_eq!(writer.position(), 8);
+    assert_eq!(&buf[..8], &[1, 0x00, 0x01, 0xf6, 0x00, 0xfe, 0xff, 0]);
+
+    let mut reader = SliceReader::new(&buf);
+    assert!(reader.try_read_le::<bool>().unwrap());
+    assert_eq!(reader.try_read_be::<char>().unwrap(), '\u{1f600}');
+    assert_eq!(reader.try_read_le::<NonZeroI16>().unwrap().get(), -2);
+    assert!(!reader.try_read_be::<bool>().unwrap());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_writer_accepts_validated_values() {
+    let mut writer = tinybit::VecWriter::with_order(tinybit::BigEndian);
+    assert_eq!(writer.try_write(&'a'), 4);
+    assert_eq!(writer.try_write_le(&true), 1);
+    assert_eq!(writer.as_slice(), &[0, 0, 0, 0x61, 1]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn streams_accept_validated_values() {
+    use tinybit::{EndianReader, EndianWriter, LittleEndian, ReadEndianExt, WriteEndianExt};
+
+    let mut buf = Vec::new();
+    buf.try_write_be(&'\u{e9}').unwrap();
+    buf.try_write_le(&NonZeroU8::new(3).unwrap()).unwrap();
+
+    let mut writer = EndianWriter::new(&mut buf, LittleEndian);
+    writer.try_write(&true).unwrap();
+    writer.try_write(&'x').unwrap();
+    assert_eq!(buf, [0, 0, 0, 0xe9, 3, 1, 0x78, 0, 0, 0]);
+
+    let mut src = &buf[..];
+    assert_eq!(src.try_read_be::<char>().unwrap(), '\u{e9}');
+    assert_eq!(src.try_read_le::<NonZeroU8>().unwrap().get(), 3);
+
+    let mut reader = EndianReader::new(src, LittleEndian);
+    assert!(reader.try_read::<bool>().unwrap());
+    assert_eq!(reader.try_read::<char>().unwrap(), 'x');
+
+    // The stream paths report the same bytes as the slice paths
+    let (offset, _, bytes) = invalid(bool::try_read_le(&mut &[5u8][..]).unwrap_err());
+    assert_eq!((offset, bytes), (0, vec![5]));
+    let (offset, _, bytes) = invalid(char::try_read_be(&mut &[0x00, 0x00, 0xdc, 0x00][..]).unwrap_err());
+    assert_eq!((offset, bytes), (0, vec![0x00, 0x00, 0xdc, 0x00]));
+}
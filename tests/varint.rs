@@ -0,0 +1,168 @@
+use tinybit::{EndianError, Leb128, QuicVarint, SliceReader, SliceWriter, Sleb128, SqliteVarint, Varint, Vlq, ZigZag};
+
+/// Encodes `value`, checks the bytes against `expected` and decodes them back
+fn round_trip<E: Varint>(value: E::Value, expected: &[u8])
+    where E::Value: PartialEq + core::fmt::Debug
+{
+    let mut buf = [0; 16];
+    let mut writer = SliceWriter::new(&mut buf);
+    assert_eq!(writer.write_varint::<E>(value).unwrap(), expected.len(), "length of {:?}", value);
+    assert_eq!(&buf[..expected.len()], expected, "encoding of {:?}", value);
+
+    let mut reader = SliceReader::new(expected);
+    assert_eq!(reader.read_varint::<E>().unwrap(), value);
+    assert_eq!(reader.position(), expected.len());
+}
+
+/// Decodes `bytes`, which must be rejected
+fn decode<E: Varint>(bytes: &[u8]) -> EndianError
+    where E::Value: core::fmt::Debug
+{
+    let mut reader = SliceReader::new(bytes);
+    let err = reader.read_varint::<E>().unwrap_err();
+    assert_eq!(reader.position(), 0);
+    err
+}
+
+#[test]
+fn leb128_round_trip() {
+    round_trip::<Leb128>(0, &[0x00]);
+    round_trip::<Leb128>(0x7f, &[0x7f]);
+    round_trip::<Leb128>(0x80, &[0x80, 0x01]);
+    round_trip::<Leb128>(0x3fff, &[0xff, 0x7f]);
+    round_trip::<Leb128>(0x4000, &[0x80, 0x80, 0x01]);
+    round_trip::<Leb128>(u64::MAX, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+}
+
+#[test]
+fn leb128_rejects_overflow_and_overlong() {
+    // Only the lowest bit of the 10th byte fits in a u64
+    let err = decode::<Leb128>(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]);
+    assert!(matches!(err, EndianError::Overflow { .. }), "{:?}", err);
+
+    // An 11th byte is never valid
+    let err = decode::<Leb128>(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00]);
+    assert!(matches!(err, EndianError::Overflow { .. }), "{:?}", err);
+
+    let err = decode::<Leb128>(&[0x80, 0x00]);
+    assert!(matches!(err, EndianError::Overlong { offset: 0 }), "{:?}", err);
+
+    let err = decode::<Leb128>(&[0x80, 0x80]);
+    assert!(matches!(err, EndianError::EndOfStream(2)), "{:?}", err);
+}
+
+#[test]
+fn sleb128_round_trip() {
+    round_trip::<Sleb128>(0, &[0x00]);
+    round_trip::<Sleb128>(-1, &[0x7f]);
+    round_trip::<Sleb128>(0x3f, &[0x3f]);
+    round_trip::<Sleb128>(0x40, &[0xc0, 0x00]);
+    round_trip::<Sleb128>(-0x40, &[0x40]);
+    round_trip::<Sleb128>(-0x41, &[0xbf, 0x7f]);
+    round_trip::<Sleb128>(i64::MAX, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]);
+    round_trip::<Sleb128>(i64::MIN, &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f]);
+}
+
+#[test]
+fn sleb128_rejects_overflow_and_overlong() {
+    // The 10th byte may only extend the sign
+    let err = decode::<Sleb128>(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01]);
+    assert!(matches!(err, EndianError::Overflow { .. }), "{:?}", err);
+
+    let err = decode::<Sleb128>(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7e]);
+    assert!(matches!(err, EndianError::Overflow { .. }), "{:?}", err);
+
+    let err = decode::<Sleb128>(&[0xff, 0x7f]);
+    assert!(matches!(err, EndianError::Overlong { offset: 0 }), "{:?}", err);
+}
+
+#[test]
+fn zigzag_round_trip() {
+    round_trip::<ZigZag>(0, &[0x00]);
+    round_trip::<ZigZag>(-1, &[0x01]);
+    round_trip::<ZigZag>(1, &[0x02]);
+    round_trip::<ZigZag>(-64, &[0x7f]);
+    round_trip::<ZigZag>(64, &[0x80, 0x01]);
+    round_trip::<ZigZag>(i64::MAX, &[0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+    round_trip::<ZigZag>(i64::MIN, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+}
+
+#[test]
+fn quic_round_trip() {
+    round_trip::<QuicVarint>(0, &[0x00]);
+    round_trip::<QuicVarint>(0x3f, &[0x3f]);
+    round_trip::<QuicVarint>(0x40, &[0x40, 0x40]);
+    round_trip::<QuicVarint>(0x3fff, &[0x7f, 0xff]);
+    round_trip::<QuicVarint>(0x4000, &[0x80, 0x00, 0x40, 0x00]);
+    round_trip::<QuicVarint>(0x4000_0000, &[0xc0, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00]);
+    round_trip::<QuicVarint>(QuicVarint::MAX, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+}
+
+#[test]
+fn quic_limits() {
+    // Values above 62 bits can not be encoded
+    let mut buf = [0; 16];
+    let err = SliceWriter::new(&mut buf).write_varint::<QuicVarint>(QuicVarint::MAX + 1).unwrap_err();
+    assert!(matches!(err, EndianError::Overflow { .. }), "{:?}", err);
+
+    let err = SliceWriter::new(&mut buf).write_varint::<QuicVarint>(u64::MAX).unwrap_err();
+    assert!(matches!(err, EndianError::Overflow { .. }), "{:?}", err);
+
+    // Longer than necessary forms are allowed by QUIC
+    assert_eq!(SliceReader::new(&[0x40, 0x01]).read_varint::<QuicVarint>().unwrap(), 1);
+    assert_eq!(SliceReader::new(&[0xc0, 0, 0, 0, 0, 0, 0, 0x01]).read_varint::<QuicVarint>().unwrap(), 1);
+}
+
+#[test]
+fn sqlite_round_trip() {
+    round_trip::<SqliteVarint>(0, &[0x00]);
+    round_trip::<SqliteVarint>(0x7f, &[0x7f]);
+    round_trip::<SqliteVarint>(0x80, &[0x81, 0x00]);
+    round_trip::<SqliteVarint>(0x3fff, &[0xff, 0x7f]);
+    round_trip::<SqliteVarint>((1 << 56) - 1, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]);
+    round_trip::<SqliteVarint>(1 << 56, &[0x80, 0xc0, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00]);
+    round_trip::<SqliteVarint>(u64::MAX, &[0xff; 9]);
+}
+
+#[test]
+fn sqlite_rejects_overlong() {
+    let err = decode::<SqliteVarint>(&[0x80, 0x01]);
+    assert!(matches!(err, EndianError::Overlong { offset: 0 }), "{:?}", err);
+
+    // The 9 byte form is only used for values above 56 bits
+    let err = decode::<SqliteVarint>(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01]);
+    assert!(matches!(err, EndianError::Overlong { offset: 0 }), "{:?}", err);
+}
+
+#[test]
+fn vlq_round_trip() {
+    round_trip::<Vlq>(0, &[0x00]);
+    round_trip::<Vlq>(0x7f, &[0x7f]);
+    round_trip::<Vlq>(0x80, &[0x81, 0x00]);
+    round_trip::<Vlq>(0x3fff, &[0xff, 0x7f]);
+    round_trip::<Vlq>(0x4000, &[0x81, 0x80, 0x00]);
+    round_trip::<Vlq>(u64::MAX, &[0x81, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]);
+}
+
+#[test]
+fn vlq_rejects_overflow_and_overlong() {
+    // The 10th group would shift a set bit out of the top of the u64
+    let err = decode::<Vlq>(&[0x82, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00]);
+    assert!(matches!(err, EndianError::Overflow { .. }), "{:?}", err);
+
+    let err = decode::<Vlq>(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00]);
+    assert!(matches!(err, EndianError::Overflow { .. }), "{:?}", err);
+
+    let err = decode::<Vlq>(&[0x80, 0x7f]);
+    assert!(matches!(err, EndianError::Overlong { offset: 0 }), "{:?}", err);
+}
+
+#[test]
+fn errors_report_the_reader_position() {
+    let mut reader = SliceReader::new(&[0x01, 0x80, 0x00]);
+    assert_eq!(reader.read_varint::<Leb128>().unwrap(), 1);
+
+    let err = reader.read_varint::<Leb128>().unwrap_err();
+    assert!(matches!(err, EndianError::Overlong { offset: 1 }), "{:?}", err);
+    assert_eq!(reader.position(), 1);
+}
@@ -0,0 +1,591 @@
+//! # Tinybit Derive
+//! Derive macros for the `tinybit` crate, enabled through its `derive` feature
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DataEnum, DeriveInput, Error, Fields, Ident, Member, Type};
+
+/// Derives `tinybit::Endian` for a struct by serializing each field in declaration order, or for an
+/// enum with a catch-all variant
+///
+/// # Remarks
+/// Each field is converted with its own byte order and padding between fields is skipped, so the
+/// encoded size is the sum of the encoded sizes of the fields. A field can be pinned to a fixed byte
+/// order regardless of the method called with `#[endian(big)]` or `#[endian(little)]`
+///
+/// Enums are encoded as described on [`TryEndian`](derive_try_endian), including the unpadded
+/// `tinybit::Tagged` encoding, and must mark a variant holding a single tag as `#[endian(unknown)]`, which
+/// receives every tag not matching another variant so decoding never fails
+#[proc_macro_derive(Endian, attributes(endian))]
+pub fn derive_endian(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_endian(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into()
+    }
+}
+
+/// Derives `tinybit::TryEndian` for an enum with an integer `#[repr]`, e.g. `#[repr(u8)]`
+///
+/// # Remarks
+/// A value is encoded as its discriminant in the `repr` type followed by the fields of the variant in
+/// declaration order. Variants with smaller payloads are padded with zeros up to the largest one, like
+/// a C tagged union, so every value has the same encoded size and fieldless enums are just the tag.
+/// Padding is skipped without being checked when decoding
+///
+/// `tinybit::Tagged` is implemented as well, encoding the tag followed by only the fields of the variant.
+/// This is a different wire format from the fixed size one above, use it for streams of messages whose
+/// variants differ in size so a small variant neither writes the padding of a large one nor reads into
+/// the next message
+///
+/// Decoding an unknown tag fails with `EndianError::InvalidValue` unless a variant holding a single
+/// tag of the `repr` type is marked `#[endian(unknown)]`, in which case the tag is stored in that
+/// variant and written back out unchanged. Fields accept the same `#[endian(big)]` and
+/// `#[endian(little)]` overrides as the `Endian` derive
+#[proc_macro_derive(TryEndian, attributes(endian))]
+pub fn derive_try_endian(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let result = match &input.data {
+        Data::Enum(data) => expand_enum(&input, data, true),
+        Data::Struct(data) => Err(Error::new(data.struct_token.span, "TryEndian can only be derived for enums")),
+        Data::Union(data) => Err(Error::new(data.union_token.span, "TryEndian can only be derived for enums"))
+    };
+
+    match result {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into()
+    }
+}
+
+/// Derives `tinybit::FromBytes` for a struct, allowing it to be viewed directly from bytes
+///
+/// # Remarks
+/// The struct must be `#[repr(C)]` or `#[repr(transparent)]`, must not be generic, every field must
+/// implement `FromBytes` and the fields must not leave any padding between them. Each requirement
+/// is checked at compile time
+#[proc_macro_derive(FromBytes)]
+pub fn derive_from_bytes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_from_bytes(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into()
+    }
+}
+
+/// Byte order of a conversion, or the override for a single field
+#[derive(Clone, Copy)]
+enum Order {
+    Little,
+    Big
+}
+
+/// A field of the derived struct along with its byte order override
+struct Field {
+    member: Member,
+    ty: Type,
+    order: Option<Order>
+}
+
+/// A variant of the derived enum
+struct Variant {
+    ident: Ident,
+    tag: TokenStream2,
+    fields: Vec<Field>,
+    unknown: bool
+}
+
+fn expand_endian(mut input: DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => parse_fields(&data.fields)?,
+        Data::Enum(data) => return expand_enum(&input, data, false),
+        Data::Union(data) => return Err(Error::new(data.union_token.span, "Endian can only be derived for structs and enums"))
+    };
+
+    // Every field must itself be Endian for the struct to be
+    {
+        let where_clause = input.generics.make_where_clause();
+        for field in &fields {
+            let ty = &field.ty;
+            where_clause.predicates.push(parse_quote!(#ty: ::tinybit::Endian));
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let tys: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let members: Vec<_> = fields.iter().map(|field| &field.member).collect();
+
+    // Offset of each field in the encoded representation
+    let offsets: Vec<_> = (0..fields.len())
+        .map(|i| {
+            let prev = &tys[..i];
+            quote!(0 #(+ <#prev as ::tinybit::Endian>::SIZE)*)
+        })
+        .collect();
+
+    let write_le_unchecked: Vec<_> = fields.iter().map(|field| field.method(Order::Little, "write_le_unchecked", "write_be_unchecked")).collect();
+    let write_be_unchecked: Vec<_> = fields.iter().map(|field| field.method(Order::Big, "write_le_unchecked", "write_be_unchecked")).collect();
+    let read_le_unchecked: Vec<_> = fields.iter().map(|field| field.method(Order::Little, "read_le_unchecked", "read_be_unchecked")).collect();
+    let read_be_unchecked: Vec<_> = fields.iter().map(|field| field.method(Order::Big, "read_le_unchecked", "read_be_unchecked")).collect();
+
+    Ok(quote! {
+        impl #impl_generics ::tinybit::Endian for #name #ty_generics #where_clause {
+            const SIZE: usize = 0 #(+ <#tys as ::tinybit::Endian>::SIZE)*;
+
+            unsafe fn write_le_unchecked(&self, out: *mut u8) -> usize {
+                unsafe {
+                    #(<#tys as ::tinybit::Endian>::#write_le_unchecked(&self.#members, out.add(#offsets));)*
+                }
+                <Self as ::tinybit::Endian>::SIZE
+            }
+
+            unsafe fn write_be_unchecked(&self, out: *mut u8) -> usize {
+                unsafe {
+                    #(<#tys as ::tinybit::Endian>::#write_be_unchecked(&self.#members, out.add(#offsets));)*
+                }
+                <Self as ::tinybit::Endian>::SIZE
+            }
+
+            unsafe fn read_le_unchecked(src: *const u8) -> Self {
+                unsafe {
+                    Self {
+                        #(#members: <#tys as ::tinybit::Endian>::#read_le_unchecked(src.add(#offsets)),)*
+                    }
+                }
+            }
+
+            unsafe fn read_be_unchecked(src: *const u8) -> Self {
+                unsafe {
+                    Self {
+                        #(#members: <#tys as ::tinybit::Endian>::#read_be_unchecked(src.add(#offsets)),)*
+                    }
+                }
+            }
+        }
+    })
+}
+
+fn expand_enum(input: &DeriveInput, data: &DataEnum, fallible: bool) -> Result<TokenStream2, Error> {
+    let repr = parse_repr(input)?;
+    let variants = parse_variants(data, &repr)?;
+    let unknown = variants.iter().find(|variant| variant.unknown).map(|variant| &variant.ident);
+    if !fallible && unknown.is_none() {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "Endian can only be derived for enums with an `#[endian(unknown)]` variant, derive TryEndian to reject unknown tags"
+        ));
+    }
+
+    // Every payload field must be Endian for the enum to be
+    let mut generics = input.generics.clone();
+    {
+        let where_clause = generics.make_where_clause();
+        for field in variants.iter().filter(|variant| !variant.unknown).flat_map(|variant| &variant.fields) {
+            let ty = &field.ty;
+            where_clause.predicates.push(parse_quote!(#ty: ::tinybit::Endian));
+        }
+    }
+
+    let name = &input.ident;
+    let type_name = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let trait_path = if fallible { quote!(::tinybit::TryEndian) } else { quote!(::tinybit::Endian) };
+    let known: Vec<_> = variants.iter().filter(|variant| !variant.unknown).collect();
+
+    // Size of the largest payload, which every variant is padded to
+    let payload_sizes: Vec<_> = known.iter().map(|variant| variant.payload_size()).collect();
+    let tag_size = quote!(<#repr as ::tinybit::Endian>::SIZE);
+    let payload_max = quote!((<Self as #trait_path>::SIZE - #tag_size));
+
+    let ok = |result: bool, value: TokenStream2| if result { quote!(::core::result::Result::Ok(#value)) } else { value };
+    let unknown_read = |result: bool, bytes: &str| match unknown {
+        Some(ident) => ok(result, quote!(Self::#ident(tag))),
+        None => {
+            let bytes = Ident::new(bytes, Span::call_site());
+            quote!(::core::result::Result::Err(::tinybit::__private::invalid_value(#type_name, &tag.#bytes())))
+        }
+    };
+
+    // Writes the tag and the fields of the variant to `out`, followed by zeros up to the largest payload
+    // when `padded`
+    let write_unchecked = |order: Order, padded: bool| {
+        let tag_method = order.method("write_le_unchecked", "write_be_unchecked");
+        let arms = known.iter().zip(&payload_sizes).map(|(variant, payload_size)| {
+            let ident = &variant.ident;
+            let tag = &variant.tag;
+            let (members, binds) = variant.bindings();
+            let tys = variant.fields.iter().map(|field| &field.ty);
+            let offsets = variant.offsets();
+            let methods = variant.fields.iter().map(|field| field.method(order, "write_le_unchecked", "write_be_unchecked"));
+            let padding = padded.then(|| quote! {
+                ::core::ptr::write_bytes(out.add(#tag_size + #payload_size), 0, #payload_max - #payload_size);
+            });
+            quote! {
+                Self::#ident { #(#members: #binds,)* } => {
+                    <#repr as ::tinybit::Endian>::#tag_method(&(#tag), out);
+                    #(<#tys as ::tinybit::Endian>::#methods(#binds, out.add(#tag_size + #offsets));)*
+                    #padding
+                }
+            }
+        });
+        let unknown_arm = unknown.map(|ident| {
+            let padding = padded.then(|| quote!(::core::ptr::write_bytes(out.add(#tag_size), 0, #payload_max);));
+            quote! {
+                Self::#ident(tag) => {
+                    <#repr as ::tinybit::Endian>::#tag_method(tag, out);
+                    #padding
+                }
+            }
+        });
+
+        quote! {
+            unsafe {
+                match self {
+                    #(#arms)*
+                    #unknown_arm
+                }
+            }
+        }
+    };
+
+    // Reads the tag and the fields of the matching variant from `src`, wrapped in `Ok` when `result`
+    let read_unchecked = |order: Order, result: bool| {
+        let tag_method = order.method("read_le_unchecked", "read_be_unchecked");
+        let checks = known.iter().map(|variant| {
+            let ident = &variant.ident;
+            let tag = &variant.tag;
+            let members = variant.fields.iter().map(|field| &field.member);
+            let tys = variant.fields.iter().map(|field| &field.ty);
+            let offsets = variant.offsets();
+            let methods = variant.fields.iter().map(|field| field.method(order, "read_le_unchecked", "read_be_unchecked"));
+            let value = ok(result, quote!(Self::#ident { #(#members: <#tys as ::tinybit::Endian>::#methods(src.add(#tag_size + #offsets)),)* }));
+            quote! {
+                if tag == (#tag) {
+                    return #value;
+                }
+            }
+        });
+        let fallback = unknown_read(result, order.method("to_le_bytes", "to_be_bytes").to_string().as_str());
+
+        quote! {
+            unsafe {
+                let tag = <#repr as ::tinybit::Endian>::#tag_method(src);
+                #(#checks)*
+                #fallback
+            }
+        }
+    };
+
+    let (write_le_unchecked, write_be_unchecked) = (write_unchecked(Order::Little, true), write_unchecked(Order::Big, true));
+    let (read_le_unchecked, read_be_unchecked) = (read_unchecked(Order::Little, fallible), read_unchecked(Order::Big, fallible));
+    let (write_le_tagged, write_be_tagged) = (write_unchecked(Order::Little, false), write_unchecked(Order::Big, false));
+    let (read_le_tagged, read_be_tagged) = (read_unchecked(Order::Little, true), read_unchecked(Order::Big, true));
+
+    // The read methods are named and typed differently by the two traits
+    let (read_le_unchecked_name, read_be_unchecked_name, read_result) = if fallible {
+        (
+            quote!(try_read_le_unchecked),
+            quote!(try_read_be_unchecked),
+            quote!(::core::result::Result<Self, ::tinybit::EndianError>)
+        )
+    } else {
+        (quote!(read_le_unchecked), quote!(read_be_unchecked), quote!(Self))
+    };
+
+    let known_idents: Vec<_> = known.iter().map(|variant| &variant.ident).collect();
+    let known_tags: Vec<_> = known.iter().map(|variant| &variant.tag).collect();
+    let unknown_len = unknown.map(|ident| quote!(Self::#ident(_) => 0,));
+    let unknown_payload = match unknown {
+        Some(_) => quote!(::core::result::Result::Ok(0)),
+        None => quote!(::core::result::Result::Err(::tinybit::__private::invalid_value(#type_name, &bytes[..#tag_size])))
+    };
+
+    Ok(quote! {
+        impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+            const SIZE: usize = #tag_size + {
+                let mut max = 0;
+                #(if #payload_sizes > max { max = #payload_sizes; })*
+                max
+            };
+
+            unsafe fn write_le_unchecked(&self, out: *mut u8) -> usize {
+                #write_le_unchecked
+                <Self as #trait_path>::SIZE
+            }
+
+            unsafe fn write_be_unchecked(&self, out: *mut u8) -> usize {
+                #write_be_unchecked
+                <Self as #trait_path>::SIZE
+            }
+
+            unsafe fn #read_le_unchecked_name(src: *const u8) -> #read_result {
+                #read_le_unchecked
+            }
+
+            unsafe fn #read_be_unchecked_name(src: *const u8) -> #read_result {
+                #read_be_unchecked
+            }
+        }
+
+        impl #impl_generics ::tinybit::Tagged for #name #ty_generics #where_clause {
+            const TAG_SIZE: usize = #tag_size;
+
+            fn tagged_len(&self) -> usize {
+                #tag_size + match self {
+                    #(Self::#known_idents { .. } => #payload_sizes,)*
+                    #unknown_len
+                }
+            }
+
+            fn payload_len<B: ::tinybit::ByteOrder>(bytes: &[u8], order: B) -> ::core::result::Result<usize, ::tinybit::EndianError> {
+                let tag = <#repr as ::tinybit::Endian>::read_from(bytes, order)?;
+                #(if tag == (#known_tags) {
+                    return ::core::result::Result::Ok(#payload_sizes);
+                })*
+                #unknown_payload
+            }
+
+            fn write_tagged_into<B: ::tinybit::ByteOrder>(&self, out: &mut [u8], order: B) -> ::core::result::Result<usize, ::tinybit::EndianError> {
+                let len = <Self as ::tinybit::Tagged>::tagged_len(self);
+                if out.len() < len {
+                    return ::core::result::Result::Err(::tinybit::EndianError::EndOfStream(0));
+                }
+
+                // Only the bytes of the variant are written, `out` was checked to hold them
+                let out = out.as_mut_ptr();
+                if order.is_big() {
+                    #write_be_tagged
+                } else {
+                    #write_le_tagged
+                }
+                ::core::result::Result::Ok(len)
+            }
+
+            fn read_tagged_from<B: ::tinybit::ByteOrder>(src: &[u8], order: B) -> ::core::result::Result<Self, ::tinybit::EndianError> {
+                let len = <Self as ::tinybit::Tagged>::payload_len(src, order)?;
+                if src.len() < #tag_size + len {
+                    return ::core::result::Result::Err(::tinybit::EndianError::EndOfStream(0));
+                }
+
+                // The tag is known and `src` was checked to hold the payload of its variant
+                let src = src.as_ptr();
+                if order.is_big() {
+                    #read_be_tagged
+                } else {
+                    #read_le_tagged
+                }
+            }
+        }
+    })
+}
+
+fn expand_from_bytes(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        Data::Enum(data) => return Err(Error::new(data.enum_token.span, "FromBytes can only be derived for structs")),
+        Data::Union(data) => return Err(Error::new(data.union_token.span, "FromBytes can only be derived for structs"))
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(&input.generics, "FromBytes can not be derived for generic structs"));
+    }
+
+    // The layout must be defined for a byte view to be meaningful
+    let mut has_repr = false;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") || meta.path.is_ident("transparent") {
+                has_repr = true;
+            }
+
+            // Skip the arguments of e.g. `packed(2)` or `align(8)`
+            if meta.input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                content.parse::<TokenStream2>()?;
+            }
+
+            Ok(())
+        })?;
+    }
+
+    if !has_repr {
+        return Err(Error::new_spanned(&input.ident, "FromBytes requires #[repr(C)] or #[repr(transparent)]"));
+    }
+
+    let name = &input.ident;
+    let tys: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+
+    Ok(quote! {
+        unsafe impl ::tinybit::FromBytes for #name
+            where #(#tys: ::tinybit::FromBytes,)*
+        { }
+
+        // A struct is free of padding when its size is the sum of the sizes of its fields
+        const _: () = ::core::assert!(
+            ::core::mem::size_of::<#name>() == 0 #(+ ::core::mem::size_of::<#tys>())*,
+            "FromBytes can not be derived for structs containing padding"
+        );
+    })
+}
+
+impl Order {
+    /// Picks the method matching this byte order
+    fn method(self, le: &str, be: &str) -> Ident {
+        let name = match self {
+            Order::Little => le,
+            Order::Big => be
+        };
+
+        Ident::new(name, Span::call_site())
+    }
+}
+
+impl Field {
+    /// Picks the `Endian` method for this field, honouring its byte order override
+    fn method(&self, order: Order, le: &str, be: &str) -> Ident {
+        self.order.unwrap_or(order).method(le, be)
+    }
+}
+
+impl Variant {
+    /// The fields of the variant paired with the names they are bound to in a pattern
+    fn bindings(&self) -> (Vec<&Member>, Vec<Ident>) {
+        let members = self.fields.iter().map(|field| &field.member).collect();
+        let binds = (0..self.fields.len()).map(|i| format_ident!("__field{}", i)).collect();
+        (members, binds)
+    }
+
+    /// Offset of each field from the start of the payload
+    fn offsets(&self) -> Vec<TokenStream2> {
+        (0..self.fields.len())
+            .map(|i| {
+                let prev = self.fields[..i].iter().map(|field| &field.ty);
+                quote!(0 #(+ <#prev as ::tinybit::Endian>::SIZE)*)
+            })
+            .collect()
+    }
+
+    /// The encoded size of the fields of the variant
+    fn payload_size(&self) -> TokenStream2 {
+        let tys = self.fields.iter().map(|field| &field.ty);
+        quote!((0 #(+ <#tys as ::tinybit::Endian>::SIZE)*))
+    }
+}
+
+/// Finds the integer type of an enum's `#[repr]`, which is used for the tag
+fn parse_repr(input: &DeriveInput) -> Result<Ident, Error> {
+    const INTS: [&str; 10] = ["u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128"];
+
+    let mut repr = None;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident().filter(|ident| INTS.iter().any(|int| ident == int)) {
+                repr = Some(ident.clone());
+            }
+
+            // Skip the arguments of e.g. `align(8)`
+            if meta.input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                content.parse::<TokenStream2>()?;
+            }
+
+            Ok(())
+        })?;
+    }
+
+    repr.ok_or_else(|| Error::new_spanned(&input.ident, "enums require an integer #[repr] for the tag, e.g. #[repr(u8)]"))
+}
+
+fn parse_variants(data: &DataEnum, repr: &Ident) -> Result<Vec<Variant>, Error> {
+    // Tags follow the discriminants, counting up from the last explicit one
+    let mut base = quote!(0);
+    let mut count = 0;
+
+    let mut result: Vec<Variant> = Vec::with_capacity(data.variants.len());
+    for variant in &data.variants {
+        if let Some((_, expr)) = &variant.discriminant {
+            base = quote!(#expr);
+            count = 0;
+        }
+
+        let step = Literal::usize_unsuffixed(count);
+        let tag = quote!((#base) + #step);
+        count += 1;
+
+        let mut unknown = false;
+        for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("endian")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("unknown") {
+                    unknown = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `unknown`"))
+                }
+            })?;
+        }
+
+        if unknown {
+            let ty = match &variant.fields {
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+                _ => return Err(Error::new_spanned(&variant.ident, "the `#[endian(unknown)]` variant must hold a single tag, e.g. `Unknown(u8)`"))
+            };
+
+            // The tag is stored as read, so it must be exactly the `#[repr]` type
+            if !matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident(repr)) {
+                return Err(Error::new_spanned(ty, format!("the `#[endian(unknown)]` variant must hold the `#[repr]` type `{}`", repr)));
+            }
+
+            if result.iter().any(|other| other.unknown) {
+                return Err(Error::new_spanned(&variant.ident, "only one variant can be `#[endian(unknown)]`"));
+            }
+        }
+
+        result.push(Variant {
+            ident: variant.ident.clone(),
+            tag,
+            fields: parse_fields(&variant.fields)?,
+            unknown
+        });
+    }
+
+    Ok(result)
+}
+
+fn parse_fields(fields: &Fields) -> Result<Vec<Field>, Error> {
+    let mut result = Vec::with_capacity(fields.len());
+    for (i, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into())
+        };
+
+        let mut order = None;
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("endian")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("big") {
+                    order = Some(Order::Big);
+                    Ok(())
+                } else if meta.path.is_ident("little") {
+                    order = Some(Order::Little);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `big` or `little`"))
+                }
+            })?;
+        }
+
+        result.push(Field {
+            member,
+            ty: field.ty.clone(),
+            order
+        });
+    }
+
+    Ok(result)
+}